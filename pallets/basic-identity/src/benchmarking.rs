@@ -1,20 +1,30 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-basic-identity
 
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
-use frame_system::RawOrigin;
+use crate::Pallet as BasicIdentity;
+use frame_benchmarking::{account, benchmarks};
+
+const SEED: u32 = 0;
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
-		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+	create_identity {
+		let who: T::AccountId = account("who", 0, SEED);
+		let origin = T::IdentityOrigin::successful_origin();
+	}: _<T::Origin>(origin, who.clone(), T::Hash::default())
+	verify {
+		assert!(Identities::<T>::contains_key(&who));
+	}
+
+	delete_identity {
+		let who: T::AccountId = account("who", 0, SEED);
+		Identities::<T>::insert(&who, T::Hash::default());
+		let origin = T::IdentityOrigin::successful_origin();
+	}: _<T::Origin>(origin, who.clone())
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert!(!Identities::<T>::contains_key(&who));
 	}
 
-	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(BasicIdentity, crate::mock::new_test_ext(1), crate::mock::Test);
 }
@@ -11,25 +11,45 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod weights;
+pub use weights::WeightInfo;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
-	use primitives::IdentityInterface;
+	use primitives::{IdentityInterface, OnIdentityChanged};
+
+	use crate::WeightInfo;
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Origin allowed to create and delete identities
+		type IdentityOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Notified whenever an identity is created or deleted
+		type OnIdentityChanged: OnIdentityChanged<Self::AccountId>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn identities)]
 	pub type Identities<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::Hash>;
 
+	/// The current storage version. Version 0 is the layout the pallet was first released with,
+	/// `Identities` mapping each account to the hash of its identity, which no migration has
+	/// changed yet. Bump it along with a migration whenever that layout changes.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::event]
@@ -49,23 +69,29 @@ pub mod pallet {
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(10_000)]
+		#[pallet::weight(
+			T::WeightInfo::create_identity().saturating_add(T::OnIdentityChanged::max_weight())
+		)]
 		pub fn create_identity(
 			origin: OriginFor<T>,
 			who: T::AccountId,
 			name: T::Hash,
 		) -> DispatchResult {
-			ensure_root(origin)?;
+			T::IdentityOrigin::ensure_origin(origin)?;
 			Self::set_identity(&who, name);
+			T::OnIdentityChanged::on_identity_set(&who);
 			Self::deposit_event(Event::<T>::IdentityCreated(who, name));
 			Ok(())
 		}
 
-		#[pallet::weight(10_000)]
+		#[pallet::weight(
+			T::WeightInfo::delete_identity().saturating_add(T::OnIdentityChanged::max_weight())
+		)]
 		pub fn delete_identity(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
-			ensure_root(origin)?;
+			T::IdentityOrigin::ensure_origin(origin)?;
 			ensure!(Self::has_identity(&who), Error::<T>::IdentityDoesNotExist);
 			Self::clear_identity(&who);
+			T::OnIdentityChanged::on_identity_cleared(&who);
 			Self::deposit_event(Event::<T>::IdentityDeleted(who));
 			Ok(())
 		}
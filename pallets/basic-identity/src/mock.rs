@@ -1,6 +1,7 @@
 use crate as pallet_basic_identity;
 use frame_support::traits::{ConstU16, ConstU64, GenesisBuild};
 use frame_system as system;
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
@@ -54,6 +55,9 @@ impl system::Config for Test {
 
 impl pallet_basic_identity::Config for Test {
 	type Event = Event;
+	type IdentityOrigin = EnsureRoot<AccountId>;
+	type OnIdentityChanged = ();
+	type WeightInfo = ();
 }
 
 impl pallet_sudo::Config for Test {
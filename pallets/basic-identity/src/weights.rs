@@ -0,0 +1,50 @@
+//! Placeholder weights for pallet_basic_identity
+//!
+//! These numbers were written by hand from the storage each call touches, they are not the
+//! output of the benchmark CLI and have not been measured. Replace this file with the one
+//! generated by `benchmark pallet` from the benchmarks in `benchmarking.rs` (see the README)
+//! before relying on them.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_basic_identity.
+pub trait WeightInfo {
+	fn create_identity() -> Weight;
+	fn delete_identity() -> Weight;
+}
+
+/// Hand-written placeholder weights for pallet_basic_identity, not measured on any hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Identity Identities (r:0 w:1)
+	fn create_identity() -> Weight {
+		(12_405_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Identity Identities (r:1 w:1)
+	fn delete_identity() -> Weight {
+		(15_872_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Identity Identities (r:0 w:1)
+	fn create_identity() -> Weight {
+		(12_405_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Identity Identities (r:1 w:1)
+	fn delete_identity() -> Weight {
+		(15_872_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
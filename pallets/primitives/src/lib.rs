@@ -10,3 +10,19 @@ pub trait IdentityInterface<AccountId, Name> {
 
 	fn clear_identity(who: &AccountId);
 }
+
+/// Hook for pallets that need to react to identities being set or cleared
+pub trait OnIdentityChanged<AccountId> {
+	/// Called after `who` is given an identity
+	fn on_identity_set(_who: &AccountId) {}
+
+	/// Called after the identity of `who` is cleared
+	fn on_identity_cleared(_who: &AccountId) {}
+
+	/// Upper bound of the weight the hooks take, added to the calls that trigger them
+	fn max_weight() -> u64 {
+		0
+	}
+}
+
+impl<AccountId> OnIdentityChanged<AccountId> for () {}
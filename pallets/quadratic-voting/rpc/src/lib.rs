@@ -0,0 +1,227 @@
+//! RPC interface for the quadratic voting pallet, exposed under the `quadraticVoting_` prefix.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_quadratic_voting::{
+	Ballot, ProposalIndex, ReferendumDetails, ReferendumIndex, Vote, VoterStatus,
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_quadratic_voting::runtime_api::QuadraticVotingApi as QuadraticVotingRuntimeApi;
+
+/// A queued proposal along with its text
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedProposalDetails<AccountId, Balance, Hash> {
+	/// Hash of the proposal
+	pub proposal_hash: Hash,
+	/// Account that submitted the proposal
+	pub proposer: AccountId,
+	/// Amount reserved from the proposer
+	pub deposit: Balance,
+	/// Number of voters that seconded the proposal
+	pub seconds: u32,
+	/// Text of the proposal, if its preimage is stored and is valid UTF-8
+	pub text: Option<String>,
+	/// Raw bytes of the proposal, if its preimage is stored
+	pub bytes: Option<Bytes>,
+}
+
+#[rpc(client, server)]
+pub trait QuadraticVotingApi<BlockHash, AccountId, Balance, Hash, BlockNumber> {
+	/// Proposals and live tallies of a referendum, `None` if it is not active
+	#[method(name = "quadraticVoting_referendum")]
+	fn referendum(
+		&self,
+		index: ReferendumIndex,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ReferendumDetails<Hash, BlockNumber>>>;
+
+	/// Referenda that are accepting votes, oldest first
+	#[method(name = "quadraticVoting_activeReferenda")]
+	fn active_referenda(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<ReferendumDetails<Hash, BlockNumber>>>;
+
+	/// Remaining points of a voter and the active referenda it voted in
+	#[method(name = "quadraticVoting_voterStatus")]
+	fn voter_status(&self, who: AccountId, at: Option<BlockHash>)
+		-> RpcResult<Option<VoterStatus>>;
+
+	/// Proposals waiting in the queue, with their text
+	#[method(name = "quadraticVoting_proposalQueue")]
+	fn proposal_queue(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<QueuedProposalDetails<AccountId, Balance, Hash>>>;
+
+	/// Block at which the next referendum will be launched
+	#[method(name = "quadraticVoting_nextLaunch")]
+	fn next_launch(&self, at: Option<BlockHash>) -> RpcResult<BlockNumber>;
+
+	/// Points that submitting `votes` would take from a voter
+	#[method(name = "quadraticVoting_ballotCost")]
+	fn ballot_cost(&self, votes: Vec<(u32, Vote)>, at: Option<BlockHash>) -> RpcResult<u32>;
+
+	/// Ballots a voter submitted on every referendum, oldest first
+	#[method(name = "quadraticVoting_ballots")]
+	fn ballots(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(ReferendumIndex, Ballot<Vec<(ProposalIndex, u32, Vote)>, Balance>)>>;
+}
+
+/// Provides RPC methods to query the state of quadratic voting.
+pub struct QuadraticVoting<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> QuadraticVoting<C, Block> {
+	/// Create new `QuadraticVoting` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(message: &'static str, e: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(Error::RuntimeError.into(), message, Some(e.to_string())))
+		.into()
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance, BlockNumber>
+	QuadraticVotingApiServer<
+		<Block as BlockT>::Hash,
+		AccountId,
+		Balance,
+		<Block as BlockT>::Hash,
+		BlockNumber,
+	> for QuadraticVoting<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: QuadraticVotingRuntimeApi<Block, AccountId, Balance, Block::Hash, BlockNumber>,
+	AccountId: Codec + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+	Balance: Codec + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+{
+	fn referendum(
+		&self,
+		index: ReferendumIndex,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<ReferendumDetails<Block::Hash, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.referendum(&at, index)
+			.map_err(|e| runtime_error("Unable to query referendum.", e))
+	}
+
+	fn active_referenda(
+		&self,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<ReferendumDetails<Block::Hash, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.active_referenda(&at)
+			.map_err(|e| runtime_error("Unable to query active referenda.", e))
+	}
+
+	fn voter_status(
+		&self,
+		who: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<VoterStatus>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.voter_status(&at, who)
+			.map_err(|e| runtime_error("Unable to query voter status.", e))
+	}
+
+	fn proposal_queue(
+		&self,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<QueuedProposalDetails<AccountId, Balance, Block::Hash>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let queued_proposals = api
+			.queued_proposals(&at)
+			.map_err(|e| runtime_error("Unable to query proposal queue.", e))?;
+
+		let mut proposals = Vec::with_capacity(queued_proposals.len());
+		for queued_proposal in queued_proposals {
+			let bytes = api
+				.proposal_text(&at, queued_proposal.proposal_hash)
+				.map_err(|e| runtime_error("Unable to query proposal text.", e))?;
+			let text = bytes.as_ref().and_then(|bytes| String::from_utf8(bytes.clone()).ok());
+
+			proposals.push(QueuedProposalDetails {
+				proposal_hash: queued_proposal.proposal_hash,
+				proposer: queued_proposal.proposer,
+				deposit: queued_proposal.deposit,
+				seconds: queued_proposal.seconds.len() as u32,
+				text,
+				bytes: bytes.map(Into::into),
+			});
+		}
+
+		Ok(proposals)
+	}
+
+	fn next_launch(&self, at: Option<Block::Hash>) -> RpcResult<BlockNumber> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.next_launch(&at)
+			.map_err(|e| runtime_error("Unable to query next launch.", e))
+	}
+
+	fn ballot_cost(&self, votes: Vec<(u32, Vote)>, at: Option<Block::Hash>) -> RpcResult<u32> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.ballot_cost(&at, votes)
+			.map_err(|e| runtime_error("Unable to estimate ballot cost.", e))
+	}
+
+	fn ballots(
+		&self,
+		who: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<(ReferendumIndex, Ballot<Vec<(ProposalIndex, u32, Vote)>, Balance>)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.ballots(&at, who).map_err(|e| runtime_error("Unable to query ballots.", e))
+	}
+}
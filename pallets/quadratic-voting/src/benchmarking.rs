@@ -4,16 +4,510 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as QuadraticVoting;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+const SEED: u32 = 0;
+
+/// Give `who` an identity, register it as a voter and fund it
+fn setup_voter<T: Config>(who: &T::AccountId) {
+	T::Identity::set_identity(who, T::Hash::default());
+	QuadraticVoting::<T>::do_register_voter(who.clone()).expect("voter has an identity; qed");
+	T::Currency::make_free_balance_be(who, BalanceOf::<T>::max_value() / 2u32.into());
+}
+
+/// Proposal text of `length` bytes, different for every `index`
+fn proposal_of(index: u32, length: u32) -> Vec<u8> {
+	let mut proposal = index.encode();
+	proposal.resize(length.max(proposal.len() as u32) as usize, 0);
+	proposal
+}
+
+/// Call proposal remarking `length` bytes
+fn remark_of<T: Config>(length: u32) -> <T as Config>::Call {
+	frame_system::Call::<T>::remark { remark: sp_std::vec![0; length as usize] }.into()
+}
+
+/// Fill the queue up to `length` proposals of the maximum length
+fn fill_queue<T: Config>(length: u32) {
+	let proposer: T::AccountId = account("proposer", 0, SEED);
+	if !QuadraticVoting::<T>::is_a_voter(&proposer) {
+		setup_voter::<T>(&proposer);
+	}
+	let mut index = 0;
+	while (QuadraticVoting::<T>::queued_proposals().len() as u32) < length {
+		let proposal = proposal_of(index, T::MaxProposalLength::get());
+		index += 1;
+		let proposal_hash = T::Hashing::hash(&proposal);
+		if QuadraticVoting::<T>::queued_proposals()
+			.iter()
+			.any(|queued_proposal| queued_proposal.proposal_hash == proposal_hash)
+		{
+			continue;
+		}
+		QuadraticVoting::<T>::submit_proposal(RawOrigin::Signed(proposer.clone()).into(), proposal)
+			.expect("queue has room for the proposal; qed");
+	}
+}
+
+/// Second every queued proposal with `seconds` different voters
+fn second_queue<T: Config>(seconds: u32) {
+	for queued_proposal in QuadraticVoting::<T>::queued_proposals() {
+		second_proposal::<T>(queued_proposal.proposal_hash, seconds);
+	}
+}
+
+/// Second the queued proposal with `proposal_hash` with `seconds` different voters
+fn second_proposal<T: Config>(proposal_hash: T::Hash, seconds: u32) {
+	for index in 0..seconds {
+		let second: T::AccountId = account("second", index, SEED);
+		if !QuadraticVoting::<T>::is_a_voter(&second) {
+			setup_voter::<T>(&second);
+		}
+		QuadraticVoting::<T>::second(RawOrigin::Signed(second).into(), proposal_hash)
+			.expect("proposal has room for seconds; qed");
+	}
+}
+
+/// Ballot with `votes` of `vote` on each of the first `length` proposals of a referendum
+fn ballot_of<T: Config>(length: u32, votes: u32, vote: Vote) -> ReferendumVotes<T> {
+	BoundedVec::truncate_from(
+		(0..length)
+			.map(|proposal_index| (proposal_index, votes, vote.clone()))
+			.collect(),
+	)
+}
+
+/// Balance that can be locked for `votes` with conviction
+fn conviction_balance_of<T: Config>(votes: u32) -> BalanceOf<T> {
+	T::MinConvictionBalance::get()
+		.saturating_mul(votes.into())
+		.max(T::Currency::minimum_balance())
+}
+
+/// Register `delegators` voters delegating to `delegate`
+fn setup_delegators<T: Config>(delegate: &T::AccountId, delegators: u32) {
+	for index in 0..delegators {
+		let delegator: T::AccountId = account("delegator", index, SEED);
+		setup_voter::<T>(&delegator);
+		QuadraticVoting::<T>::delegate(RawOrigin::Signed(delegator).into(), delegate.clone())
+			.expect("delegate has room for delegators; qed");
+	}
+}
+
+/// Start a referendum with every proposal receiving votes from `voters` different voters
+fn setup_referendum<T: Config>(voters: u32) -> ReferendumIndex {
+	fill_queue::<T>(T::ProposalQueueSize::get());
+	let referendum_index = QuadraticVoting::<T>::referendum_count();
+	QuadraticVoting::<T>::start_referendum(referendum_index, T::LaunchPeriod::get())
+		.expect("queue holds enough proposals; qed");
+	for index in 0..voters {
+		let voter: T::AccountId = account("voter", index, SEED);
+		setup_voter::<T>(&voter);
+		let votes = ballot_of::<T>(T::ProposalsPerReferendum::get(), 1, Vote::Aye);
+		QuadraticVoting::<T>::submit_votes(
+			RawOrigin::Signed(voter).into(),
+			referendum_index,
+			votes,
+		)
+		.expect("voter has enough points; qed");
+	}
+	referendum_index
+}
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
+	// The proposal takes the last place left in the queue
+	submit_proposal {
+		let l in 1 .. T::MaxProposalLength::get();
+		fill_queue::<T>(T::ProposalQueueSize::get() - 1);
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let proposal = proposal_of(u32::MAX, l);
+	}: _(RawOrigin::Signed(caller), proposal)
+	verify {
+		let queued_proposals = QuadraticVoting::<T>::queued_proposals();
+		assert_eq!(queued_proposals.len() as u32, T::ProposalQueueSize::get());
+	}
+
+	submit_proposal_hash {
+		fill_queue::<T>(T::ProposalQueueSize::get() - 1);
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let proposal = proposal_of(u32::MAX, T::MaxProposalLength::get());
+		let proposal_hash = T::Hashing::hash(&proposal);
+		QuadraticVoting::<T>::note_preimage(RawOrigin::Signed(caller.clone()).into(), proposal)?;
+	}: _(RawOrigin::Signed(caller), proposal_hash)
+	verify {
+		let queued_proposals = QuadraticVoting::<T>::queued_proposals();
+		assert_eq!(queued_proposals.last().unwrap().proposal_hash, proposal_hash);
+	}
+
+	submit_call_proposal {
+		let l in 0 .. T::MaxProposalLength::get() / 2;
+		fill_queue::<T>(T::ProposalQueueSize::get() - 1);
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let call = remark_of::<T>(l);
+	}: _(RawOrigin::Signed(caller), Box::new(call))
+	verify {
+		let queued_proposals = QuadraticVoting::<T>::queued_proposals();
+		assert_eq!(queued_proposals.last().unwrap().kind, ProposalKind::Call);
+	}
+
+	submit_call_proposal_hash {
+		fill_queue::<T>(T::ProposalQueueSize::get() - 1);
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let call = remark_of::<T>(T::MaxProposalLength::get() / 2).encode();
+		let proposal_hash = T::Hashing::hash(&call);
+		QuadraticVoting::<T>::note_preimage(RawOrigin::Signed(caller.clone()).into(), call)?;
+	}: _(RawOrigin::Signed(caller), proposal_hash)
+	verify {
+		let queued_proposals = QuadraticVoting::<T>::queued_proposals();
+		assert_eq!(queued_proposals.last().unwrap().kind, ProposalKind::Call);
+	}
+
+	note_preimage {
+		let l in 1 .. T::MaxProposalLength::get();
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let proposal = proposal_of(0, l);
+		let proposal_hash = T::Hashing::hash(&proposal);
+	}: _(RawOrigin::Signed(caller), proposal)
+	verify {
+		assert!(ProposalPreimages::<T>::contains_key(proposal_hash));
+	}
+
+	second {
+		fill_queue::<T>(T::ProposalQueueSize::get());
+		second_queue::<T>(T::MaxSeconds::get() - 1);
+		let proposal_hash = QuadraticVoting::<T>::queued_proposals()[0].proposal_hash;
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), proposal_hash)
+	verify {
+		let seconds = QuadraticVoting::<T>::queued_proposals()[0].seconds;
+		assert_eq!(seconds, T::MaxSeconds::get());
+	}
+
+	clear_preimage {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let proposal = proposal_of(0, T::MaxProposalLength::get());
+		let proposal_hash = T::Hashing::hash(&proposal);
+		QuadraticVoting::<T>::note_preimage(RawOrigin::Signed(caller.clone()).into(), proposal)?;
+	}: _(RawOrigin::Signed(caller), proposal_hash)
+	verify {
+		assert!(!ProposalPreimages::<T>::contains_key(proposal_hash));
+	}
+
+	submit_votes {
+		let v in 1 .. T::ProposalsPerReferendum::get();
+		let d in 0 .. T::MaxDelegators::get();
+		fill_queue::<T>(T::ProposalQueueSize::get());
+		QuadraticVoting::<T>::start_referendum(0, T::LaunchPeriod::get())?;
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		setup_delegators::<T>(&caller, d);
+		let votes = ballot_of::<T>(v, 1, Vote::Aye);
+	}: _(RawOrigin::Signed(caller.clone()), 0, votes)
+	verify {
+		assert!(VotingOf::<T>::contains_key(0, &caller));
+	}
+
+	submit_votes_with_conviction {
+		let v in 1 .. T::ProposalsPerReferendum::get();
+		let d in 0 .. T::MaxDelegators::get();
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		setup_delegators::<T>(&caller, d);
+		// Extend a lock taken on an earlier referendum, which is the heaviest path
+		let locked_referendum = setup_referendum::<T>(0);
+		QuadraticVoting::<T>::submit_votes_with_conviction(
+			RawOrigin::Signed(caller.clone()).into(),
+			locked_referendum,
+			ballot_of::<T>(1, 1, Vote::Aye),
+			Conviction::Locked2x,
+			conviction_balance_of::<T>(1),
+		)?;
+		let referendum_index = setup_referendum::<T>(0);
+		let votes = ballot_of::<T>(v, 1, Vote::Aye);
+		let balance = T::Currency::free_balance(&caller);
+	}: _(RawOrigin::Signed(caller.clone()), referendum_index, votes, Conviction::Locked6x, balance)
+	verify {
+		assert_eq!(QuadraticVoting::<T>::conviction_lock_of(&caller).unwrap().balance, balance);
+	}
+
+	// Every delegator's ballot is replaced along with the delegate's, the old ballots vote on
+	// `o` proposals
+	change_votes {
+		let v in 1 .. T::ProposalsPerReferendum::get();
+		let o in 1 .. T::ProposalsPerReferendum::get();
+		let d in 0 .. T::MaxDelegators::get();
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		setup_delegators::<T>(&caller, d);
+		let referendum_index = setup_referendum::<T>(0);
+		let votes = ballot_of::<T>(o, 1, Vote::Aye);
+		QuadraticVoting::<T>::submit_votes(
+			RawOrigin::Signed(caller.clone()).into(),
+			referendum_index,
+			votes,
+		)?;
+		let new_votes = ballot_of::<T>(v, 2, Vote::Nay);
+	}: _(RawOrigin::Signed(caller.clone()), referendum_index, new_votes.clone())
+	verify {
+		assert_eq!(VotingOf::<T>::get(referendum_index, &caller).unwrap().votes, new_votes);
+	}
+
+	remove_votes {
+		let d in 0 .. T::MaxDelegators::get();
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		setup_delegators::<T>(&caller, d);
+		let referendum_index = setup_referendum::<T>(0);
+		let votes = ballot_of::<T>(T::ProposalsPerReferendum::get(), 1, Vote::Aye);
+		QuadraticVoting::<T>::submit_votes(
+			RawOrigin::Signed(caller.clone()).into(),
+			referendum_index,
+			votes,
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), referendum_index)
+	verify {
+		assert!(!VotingOf::<T>::contains_key(referendum_index, &caller));
+	}
+
+	unlock {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let referendum_index = setup_referendum::<T>(0);
+		QuadraticVoting::<T>::submit_votes_with_conviction(
+			RawOrigin::Signed(caller.clone()).into(),
+			referendum_index,
+			ballot_of::<T>(T::ProposalsPerReferendum::get(), 1, Vote::Aye),
+			Conviction::Locked6x,
+			conviction_balance_of::<T>(T::ProposalsPerReferendum::get()),
+		)?;
+		let unlock_at = QuadraticVoting::<T>::conviction_lock_of(&caller).unwrap().unlock_at;
+		frame_system::Pallet::<T>::set_block_number(unlock_at);
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone())
+	verify {
+		assert!(QuadraticVoting::<T>::conviction_lock_of(&caller).is_none());
+	}
+
+	// The delegate voted on every active referendum, so each ballot is cast for the caller
+	delegate {
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		setup_voter::<T>(&caller);
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		setup_voter::<T>(&delegate);
+		for _ in 0..T::MaxActiveReferenda::get() {
+			let referendum_index = setup_referendum::<T>(0);
+			QuadraticVoting::<T>::submit_votes(
+				RawOrigin::Signed(delegate.clone()).into(),
+				referendum_index,
+				ballot_of::<T>(T::ProposalsPerReferendum::get(), 1, Vote::Aye),
+			)?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), delegate.clone())
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert_eq!(QuadraticVoting::<T>::delegation_of(&caller), Some(delegate));
+		for referendum_index in QuadraticVoting::<T>::active_referenda() {
+			assert!(VotingOf::<T>::contains_key(referendum_index, &caller));
+		}
+	}
+
+	undelegate {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		setup_voter::<T>(&delegate);
+		QuadraticVoting::<T>::delegate(RawOrigin::Signed(caller.clone()).into(), delegate.clone())?;
+		let referendum_index = setup_referendum::<T>(0);
+		QuadraticVoting::<T>::submit_votes(
+			RawOrigin::Signed(delegate).into(),
+			referendum_index,
+			ballot_of::<T>(T::ProposalsPerReferendum::get(), 1, Vote::Aye),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert!(!VotingOf::<T>::contains_key(referendum_index, &caller));
+	}
+
+	register_voter {
+		let voter: T::AccountId = account("voter", 0, SEED);
+		T::Identity::set_identity(&voter, T::Hash::default());
+		let origin = T::RegistrationOrigin::successful_origin();
+	}: _<T::Origin>(origin, voter.clone())
+	verify {
+		assert!(VoterPoints::<T>::contains_key(&voter));
+	}
+
+	// The caller voted on `r` active referenda, each ballot cast for its `d` delegators too
+	deregister_voter {
+		let r in 0 .. T::MaxActiveReferenda::get();
+		let d in 0 .. T::MaxDelegators::get();
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		setup_delegators::<T>(&caller, d);
+		for _ in 0..r {
+			let referendum_index = setup_referendum::<T>(0);
+			QuadraticVoting::<T>::submit_votes(
+				RawOrigin::Signed(caller.clone()).into(),
+				referendum_index,
+				ballot_of::<T>(T::ProposalsPerReferendum::get(), 1, Vote::Aye),
+			)?;
+		}
+	}: _(RawOrigin::Signed(caller.clone()), caller.clone())
+	verify {
+		assert!(!QuadraticVoting::<T>::is_a_voter(&caller));
+		assert!(QuadraticVoting::<T>::delegators_of(&caller).is_empty());
+		for referendum_index in QuadraticVoting::<T>::active_referenda() {
+			assert!(!VotingOf::<T>::contains_key(referendum_index, &caller));
+		}
+	}
+
+	slash_proposal {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let proposal = proposal_of(0, T::MaxProposalLength::get());
+		let proposal_hash = T::Hashing::hash(&proposal);
+		QuadraticVoting::<T>::submit_proposal(RawOrigin::Signed(caller).into(), proposal)?;
+		fill_queue::<T>(T::ProposalQueueSize::get());
+		second_queue::<T>(T::MaxSeconds::get());
+		let origin = T::ModeratorOrigin::successful_origin();
+	}: _<T::Origin>(origin, proposal_hash)
+	verify {
+		let queued_proposals = QuadraticVoting::<T>::queued_proposals();
+		assert_eq!(queued_proposals.len() as u32, T::ProposalQueueSize::get() - 1);
+	}
+
+	withdraw_proposal {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let proposal = proposal_of(0, T::MaxProposalLength::get());
+		let proposal_hash = T::Hashing::hash(&proposal);
+		QuadraticVoting::<T>::submit_proposal(RawOrigin::Signed(caller.clone()).into(), proposal)?;
+		fill_queue::<T>(T::ProposalQueueSize::get());
+		second_queue::<T>(T::MaxSeconds::get());
+	}: _(RawOrigin::Signed(caller), proposal_hash)
+	verify {
+		let queued_proposals = QuadraticVoting::<T>::queued_proposals();
+		assert_eq!(queued_proposals.len() as u32, T::ProposalQueueSize::get() - 1);
+	}
+
+	remove_proposal {
+		let caller: T::AccountId = whitelisted_caller();
+		setup_voter::<T>(&caller);
+		let proposal = proposal_of(0, T::MaxProposalLength::get());
+		let proposal_hash = T::Hashing::hash(&proposal);
+		QuadraticVoting::<T>::submit_proposal(RawOrigin::Signed(caller).into(), proposal)?;
+		fill_queue::<T>(T::ProposalQueueSize::get());
+		second_queue::<T>(T::MaxSeconds::get());
+		let origin = T::ModeratorOrigin::successful_origin();
+	}: _<T::Origin>(origin, proposal_hash)
+	verify {
+		let queued_proposals = QuadraticVoting::<T>::queued_proposals();
+		assert_eq!(queued_proposals.len() as u32, T::ProposalQueueSize::get() - 1);
+	}
+
+	cancel_referendum {
+		let v in 1 .. 1_000;
+		let referendum_index = setup_referendum::<T>(v);
+		let origin = T::CancellationOrigin::successful_origin();
+	}: _<T::Origin>(origin, referendum_index, v)
+	verify {
+		assert!(QuadraticVoting::<T>::active_referenda().is_empty());
+		let voter: T::AccountId = account("voter", 0, SEED);
+		assert_eq!(QuadraticVoting::<T>::voter_points(&voter), Some(T::InitialPoints::get()));
+	}
+
+	veto {
+		let v in 1 .. 1_000;
+		let referendum_index = setup_referendum::<T>(v);
+		let origin = T::VetoOrigin::successful_origin();
+	}: _<T::Origin>(origin, referendum_index, 0, v)
+	verify {
+		assert!(QuadraticVoting::<T>::active_referenda().is_empty());
+		assert!(Blacklist::<T>::iter().next().is_some());
+	}
+
+	start_referendum {
+		fill_queue::<T>(T::ProposalQueueSize::get());
+		second_queue::<T>(T::MaxSeconds::get());
+	}: {
+		QuadraticVoting::<T>::start_referendum(0, T::LaunchPeriod::get())
+			.map_err(|_| "referendum could not start")?;
+	}
+	verify {
+		assert_eq!(QuadraticVoting::<T>::active_referenda().len(), 1);
+	}
+
+	// Only the first `p` proposals of a full queue have expired
+	expire_proposals {
+		let p in 0 .. T::MaxExpiredProposals::get().min(T::ProposalQueueSize::get());
+		fill_queue::<T>(T::ProposalQueueSize::get());
+		second_queue::<T>(T::MaxSeconds::get());
+		QueuedProposals::<T>::mutate(|queued_proposals| {
+			for queued_proposal in queued_proposals.iter_mut().skip(p as usize) {
+				queued_proposal.expires_at = T::BlockNumber::max_value();
+			}
+		});
+		let now = T::BlockNumber::max_value() - One::one();
+	}: {
+		QuadraticVoting::<T>::expire_proposals(now);
+	}
+	verify {
+		let queued_proposals = QuadraticVoting::<T>::queued_proposals();
+		assert_eq!(queued_proposals.len() as u32, T::ProposalQueueSize::get() - p);
+	}
+
+	end_referendum {
+		let referendum_index = setup_referendum::<T>(1);
+	}: {
+		QuadraticVoting::<T>::end_referendum(referendum_index)
+			.map_err(|_| "referendum could not end")?;
+	}
+	verify {
+		assert!(QuadraticVoting::<T>::active_referenda().is_empty());
+		let voter: T::AccountId = account("voter", 0, SEED);
+		let status = QuadraticVoting::<T>::voter_status(&voter).ok_or("voter is registered")?;
+		assert_eq!(status.points, T::InitialPoints::get());
+	}
+
+	// The summaries of the referendum `T::MaxSummarizedReferenda` before are removed too
+	prune_referendum {
+		let v in 1 .. 1_000;
+		let summarized = T::MaxSummarizedReferenda::get();
+		ReferendumCount::<T>::put(summarized);
+		NextToPrune::<T>::put(summarized);
+		for proposal_index in 0..T::ProposalsPerReferendum::get() {
+			let summary = ProposalSummary {
+				proposal_hash: T::Hash::default(),
+				approved: true,
+				tally: Tally::default(),
+			};
+			ReferendumSummaries::<T>::insert(0, proposal_index, summary);
+		}
+		let referendum_index = setup_referendum::<T>(v);
+		QuadraticVoting::<T>::end_referendum(referendum_index)
+			.map_err(|_| "referendum could not end")?;
+	}: {
+		QuadraticVoting::<T>::prune_referendum(T::BlockNumber::max_value(), Weight::MAX);
+	}
+	verify {
+		assert_eq!(QuadraticVoting::<T>::next_to_prune(), referendum_index + 1);
+		assert!(!ReferendumStatusOf::<T>::contains_key(referendum_index));
+		assert!(!ReferendumSummaries::<T>::contains_key(0, 0));
+		let voter: T::AccountId = account("voter", 0, SEED);
+		assert_eq!(QuadraticVoting::<T>::voter_points(&voter), Some(T::InitialPoints::get()));
+	}
+
+	enact_proposal {
+		let call = remark_of::<T>(T::MaxProposalLength::get() / 2).encode();
+	}: {
+		QuadraticVoting::<T>::enact(0, 0, ProposalKind::Call, &call);
 	}
 
 	impl_benchmark_test_suite!(QuadraticVoting, crate::mock::new_test_ext(), crate::mock::Test);
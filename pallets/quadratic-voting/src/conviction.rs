@@ -0,0 +1,57 @@
+//! Conviction a voter can lock balance with to multiply the votes of their ballot
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// How long a voter locks balance after the referendum ends, in exchange for more votes
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Conviction {
+	/// No lock, votes count once
+	None,
+	/// Votes count twice, balance is locked for one conviction period
+	Locked2x,
+	/// Votes count three times, balance is locked for two conviction periods
+	Locked3x,
+	/// Votes count four times, balance is locked for four conviction periods
+	Locked4x,
+	/// Votes count five times, balance is locked for eight conviction periods
+	Locked5x,
+	/// Votes count six times, balance is locked for sixteen conviction periods
+	Locked6x,
+}
+
+impl Default for Conviction {
+	fn default() -> Self {
+		Conviction::None
+	}
+}
+
+impl Conviction {
+	/// Factor the votes of a ballot are multiplied by
+	pub fn votes_multiplier(self) -> u32 {
+		match self {
+			Conviction::None => 1,
+			Conviction::Locked2x => 2,
+			Conviction::Locked3x => 3,
+			Conviction::Locked4x => 4,
+			Conviction::Locked5x => 5,
+			Conviction::Locked6x => 6,
+		}
+	}
+
+	/// Conviction periods the balance stays locked for after the referendum ends
+	pub fn lock_periods(self) -> u32 {
+		match self {
+			Conviction::None => 0,
+			Conviction::Locked2x => 1,
+			Conviction::Locked3x => 2,
+			Conviction::Locked4x => 4,
+			Conviction::Locked5x => 8,
+			Conviction::Locked6x => 16,
+		}
+	}
+}
@@ -11,29 +11,66 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod conviction;
+pub mod migrations;
+pub mod runtime_api;
+pub mod threshold;
 mod types;
-
-pub use types::{FinishedProposalInfo, OngoingProposalInfo, ProposalInfo, Vote};
-
-use frame_support::{dispatch::Weight, pallet_prelude::*};
+pub mod vote_cost;
+pub mod weights;
+
+pub use conviction::Conviction;
+pub use threshold::{ApprovalRule, VoteThreshold};
+pub use types::{
+	Ballot, ConvictionLock, FinishedProposalInfo, OngoingProposalInfo, ProposalDetails,
+	ProposalInfo, ProposalKind, ProposalPreimage, ProposalSummary, QueuedProposal,
+	ReferendumDetails, ReferendumStatus, Tally, Vote, VoterStatus,
+};
+
+use frame_support::{
+	dispatch::{GetDispatchInfo, Weight},
+	pallet_prelude::*,
+	storage::with_storage_layer,
+	traits::{Currency, LockIdentifier, LockableCurrency, ReservableCurrency, WithdrawReasons},
+};
 use frame_system::pallet_prelude::{BlockNumberFor, *};
-use primitives::IdentityInterface;
+use primitives::{IdentityInterface, OnIdentityChanged};
 use sp_core::Hasher;
-use sp_runtime::traits::{Saturating, Zero};
-use sp_std::vec::Vec;
+use sp_runtime::traits::{Dispatchable, One, Saturating, Zero};
+use sp_std::{boxed::Box, cmp::Reverse, vec::Vec};
+pub use vote_cost::{LinearCost, QuadraticCost, VoteCost};
+pub use weights::WeightInfo;
 
 pub type ReferendumIndex = u32;
 pub type ProposalIndex = u32;
 
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+const CONVICTION_ID: LockIdentifier = *b"qvconvic";
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
 
-	type Proposal<T> = BoundedVec<u8, <T as Config>::MaxProposalLength>;
-	type Points = u32;
+	pub type Proposal<T> = BoundedVec<u8, <T as Config>::MaxProposalLength>;
+	pub type Points = u32;
+	pub type QueuedProposalOf<T> = QueuedProposal<
+		<T as frame_system::Config>::AccountId,
+		BalanceOf<T>,
+		<T as frame_system::Config>::Hash,
+		<T as frame_system::Config>::BlockNumber,
+		u32,
+	>;
+	pub type ProposalPreimageOf<T> =
+		ProposalPreimage<<T as frame_system::Config>::AccountId, BalanceOf<T>, Proposal<T>>;
 
 	/// Distribution of votes in all proposals
-	pub type ReferendumVotes<T> = BoundedVec<(u32, Vote), <T as Config>::ProposalsPerReferendum>;
+	pub type ReferendumVotes<T> =
+		BoundedVec<(ProposalIndex, u32, Vote), <T as Config>::ProposalsPerReferendum>;
+	pub type BallotOf<T> = Ballot<ReferendumVotes<T>, BalanceOf<T>>;
+	pub type ConvictionLockOf<T> =
+		ConvictionLock<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -48,15 +85,37 @@ pub mod pallet {
 		#[pallet::constant]
 		type ProposalQueueSize: Get<u32>;
 
+		/// Blocks a proposal can wait in the queue before it expires
+		#[pallet::constant]
+		type ProposalLifetime: Get<Self::BlockNumber>;
+
+		/// Expired proposals dropped from the queue at most each time a referendum launches,
+		/// the rest are dropped at the next launches
+		#[pallet::constant]
+		type MaxExpiredProposals: Get<u32>;
+
+		/// Maximum voters that can second the same proposal
+		#[pallet::constant]
+		type MaxSeconds: Get<u32>;
+
+		/// Amount reserved from a voter seconding a proposal, returned once the proposal leaves
+		/// the queue. Can be zero to let voters second for free.
+		#[pallet::constant]
+		type SecondDeposit: Get<BalanceOf<Self>>;
+
 		/// How often (in blocks) new referenda are ran
 		#[pallet::constant]
 		type LaunchPeriod: Get<Self::BlockNumber>;
 
 		/// How long (in blocks) referenda allow votes for until they end
-		/// Needs to be lower than `LaunchPeriod` to work well
+		/// Can be longer than `LaunchPeriod`, in which case referenda overlap
 		#[pallet::constant]
 		type VotingPeriod: Get<Self::BlockNumber>;
 
+		/// Maximum number of referenda that can be active at the same time
+		#[pallet::constant]
+		type MaxActiveReferenda: Get<u32>;
+
 		/// Number of proposals to be voted per referendum
 		#[pallet::constant]
 		type ProposalsPerReferendum: Get<u32>;
@@ -65,22 +124,164 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxVotes: Get<u32>;
 
+		/// Points each voter is granted when registered
+		#[pallet::constant]
+		type InitialPoints: Get<Points>;
+
+		/// Maximum voters that can delegate to the same voter
+		#[pallet::constant]
+		type MaxDelegators: Get<u32>;
+
+		/// Points it costs to cast votes, `QuadraticCost` for quadratic voting
+		type VoteCost: VoteCost;
+
 		/// Identity pallet, used to allow users to register as voters
 		type Identity: IdentityInterface<Self::AccountId, Self::Hash>;
+
+		/// Currency used to hold proposal deposits and lock balance for conviction votes
+		type Currency: ReservableCurrency<Self::AccountId>
+			+ LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+
+		/// Base amount reserved when submitting a proposal
+		#[pallet::constant]
+		type ProposalDepositBase: Get<BalanceOf<Self>>;
+
+		/// Amount reserved per byte of proposal text, on top of `ProposalDepositBase`
+		#[pallet::constant]
+		type ProposalDepositPerByte: Get<BalanceOf<Self>>;
+
+		/// Amount reserved per byte when noting a preimage with `note_preimage`
+		#[pallet::constant]
+		type PreimageByteDeposit: Get<BalanceOf<Self>>;
+
+		/// Blocks balance stays locked after a referendum ends for each period of conviction
+		#[pallet::constant]
+		type ConvictionPeriod: Get<Self::BlockNumber>;
+
+		/// Balance that has to be locked for each vote of a ballot cast with conviction
+		#[pallet::constant]
+		type MinConvictionBalance: Get<BalanceOf<Self>>;
+
+		/// The overarching origin. Approved calls are dispatched from this pallet's `Origin`,
+		/// and approved root calls from the root origin.
+		type RuntimeOrigin: From<Origin> + From<frame_system::RawOrigin<Self::AccountId>>;
+
+		/// Calls that proposals can carry, dispatched once approved
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::RuntimeOrigin>
+			+ GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+
+		/// Blocks approved calls wait before being dispatched, zero to dispatch them as soon as
+		/// the referendum ends
+		#[pallet::constant]
+		type EnactmentPeriod: Get<Self::BlockNumber>;
+
+		/// Largest weight the call of a proposal can take to dispatch
+		#[pallet::constant]
+		type MaxEnactmentWeight: Get<Weight>;
+
+		/// Turnout and majority needed to approve text proposals
+		#[pallet::constant]
+		type TextApprovalRule: Get<ApprovalRule>;
+
+		/// Turnout and majority needed to approve call proposals
+		#[pallet::constant]
+		type CallApprovalRule: Get<ApprovalRule>;
+
+		/// Turnout and majority needed to approve root call proposals
+		#[pallet::constant]
+		type RootCallApprovalRule: Get<ApprovalRule>;
+
+		/// Blocks the full record of a finished referendum is kept for before being pruned down
+		/// to a summary of its results
+		#[pallet::constant]
+		type RetentionPeriod: Get<Self::BlockNumber>;
+
+		/// Pruned referenda whose summaries are kept, the summaries of older ones are removed
+		#[pallet::constant]
+		type MaxSummarizedReferenda: Get<u32>;
+
+		/// Origin allowed to register and deregister any account as a voter, besides the
+		/// account itself
+		type RegistrationOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to cancel an active referendum
+		type CancellationOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Origin allowed to veto a proposal, cancelling its referendum and blacklisting it
+		type VetoOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Blocks a vetoed proposal can't be submitted again for
+		#[pallet::constant]
+		type CooldownPeriod: Get<Self::BlockNumber>;
+
+		/// Origin allowed to remove spam proposals from the queue, slashing their deposit
+		type ModeratorOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for extrinsics and hooks in this pallet
+		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
-	/// Proposals that are queued to be used in the next referendum
-	/// T::ProposalsPerReferendum have to be queued for a referendum to start
+	/// Proposals that are queued to be used in the next referendum, in the order they were
+	/// submitted. T::ProposalsPerReferendum have to be queued for a referendum to start, which
+	/// picks the most seconded ones.
 	#[pallet::storage]
 	#[pallet::getter(fn queued_proposals)]
 	pub type QueuedProposals<T: Config> =
-		StorageValue<_, BoundedVec<Proposal<T>, T::ProposalQueueSize>, ValueQuery>;
+		StorageValue<_, BoundedVec<QueuedProposalOf<T>, T::ProposalQueueSize>, ValueQuery>;
+
+	/// Deposits reserved from the voters that seconded each queued proposal, keyed by the hash
+	/// of the proposal. They are kept apart from the queue so that it stays small.
+	#[pallet::storage]
+	#[pallet::getter(fn second_deposit_of)]
+	pub type SecondsOf<T: Config> =
+		StorageDoubleMap<_, Identity, T::Hash, Blake2_128Concat, T::AccountId, BalanceOf<T>>;
+
+	/// Text of proposals, keyed by their hash.
+	/// A preimage is kept while a queued proposal or an ongoing referendum refers to it.
+	#[pallet::storage]
+	#[pallet::getter(fn proposal_preimages)]
+	pub type ProposalPreimages<T: Config> = StorageMap<_, Identity, T::Hash, ProposalPreimageOf<T>>;
+
+	/// Results of the proposals of the latest `T::MaxSummarizedReferenda` pruned referenda
+	#[pallet::storage]
+	#[pallet::getter(fn summary_of)]
+	pub type ReferendumSummaries<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ReferendumIndex,
+		Twox64Concat,
+		ProposalIndex,
+		ProposalSummary<T::Hash>,
+	>;
+
+	/// Oldest referendum that hasn't been pruned yet
+	#[pallet::storage]
+	#[pallet::getter(fn next_to_prune)]
+	pub type NextToPrune<T> = StorageValue<_, ReferendumIndex, ValueQuery>;
+
+	/// Vetoed proposals, keyed by their hash, and the block until which they can't be submitted
+	#[pallet::storage]
+	#[pallet::getter(fn blacklisted_until)]
+	pub type Blacklist<T: Config> = StorageMap<_, Identity, T::Hash, T::BlockNumber>;
+
+	/// Blacklisted proposal hashes, keyed by the block their cooldown ends at, so the entries
+	/// can be removed from `Blacklist` once they expire
+	#[pallet::storage]
+	pub type BlacklistExpiries<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Identity, T::Hash, ()>;
 
-	/// Referenda that have taken place thus far, also works as an index to the last referendum
+	/// Referenda that have been started or scheduled thus far, also works as the index of the next
+	/// referendum
 	#[pallet::storage]
 	#[pallet::getter(fn referendum_count)]
 	pub type ReferendumCount<T> = StorageValue<_, ReferendumIndex, ValueQuery>;
@@ -100,25 +301,137 @@ pub mod pallet {
 		ProposalInfo<T::Hash, T::BlockNumber>,
 	>;
 
-	/// Time (in blocks) when the current referendum (if any) will end.
-	/// Currently, only one referendum can be active at a time.
+	/// Deposits held for the proposals of active referenda, returned once the referendum ends
+	#[pallet::storage]
+	#[pallet::getter(fn proposal_deposit_of)]
+	pub type ProposalDeposits<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ReferendumIndex,
+		Twox64Concat,
+		ProposalIndex,
+		(T::AccountId, BalanceOf<T>),
+	>;
+
+	/// Stage of its lifecycle each referendum is in, removed once the referendum is pruned
+	#[pallet::storage]
+	#[pallet::getter(fn referendum_status)]
+	pub type ReferendumStatusOf<T: Config> =
+		StorageMap<_, Twox64Concat, ReferendumIndex, ReferendumStatus<BlockNumberFor<T>>>;
+
+	/// Referenda that are scheduled or accepting votes right now, oldest first
+	#[pallet::storage]
+	#[pallet::getter(fn active_referenda)]
+	pub type ActiveReferenda<T: Config> =
+		StorageValue<_, BoundedVec<ReferendumIndex, T::MaxActiveReferenda>, ValueQuery>;
+
+	/// Encoded calls of approved proposals and their kind, keyed by the block they will be
+	/// dispatched at
+	#[pallet::storage]
+	#[pallet::getter(fn enactment_of)]
+	pub type Enactments<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		Twox64Concat,
+		(ReferendumIndex, ProposalIndex),
+		(ProposalKind, Proposal<T>),
+	>;
+
+	/// Ballot each voter submitted on a referendum.
+	/// Records are kept after the referendum ends so voters can look up their past ballots.
+	#[pallet::storage]
+	#[pallet::getter(fn voting_of)]
+	pub type VotingOf<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ReferendumIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		BallotOf<T>,
+	>;
+
+	/// Referenda whose ballot points each voter is yet to get back. Points are refunded the next
+	/// time the voter votes, or once the ballot is pruned.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_refunds_of)]
+	pub type PendingRefunds<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<ReferendumIndex, T::MaxActiveReferenda>,
+	>;
+
+	/// Number of voters and points spent on each proposal of an active referendum, kept in its
+	/// results once it ends
+	#[pallet::storage]
+	#[pallet::getter(fn participation_of)]
+	pub type ProposalParticipation<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ReferendumIndex,
+		Twox64Concat,
+		ProposalIndex,
+		(u32, Points),
+		ValueQuery,
+	>;
+
+	/// Voter each voter delegates to
+	#[pallet::storage]
+	#[pallet::getter(fn delegation_of)]
+	pub type Delegations<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
+	/// Voters delegating to each voter
+	#[pallet::storage]
+	#[pallet::getter(fn delegators_of)]
+	pub type Delegators<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::AccountId, T::MaxDelegators>,
+		ValueQuery,
+	>;
+
+	/// Delegate that cast each ballot in `VotingOf` on behalf of a delegator.
+	/// Ballots the voter submitted directly have no entry.
 	#[pallet::storage]
-	#[pallet::getter(fn referendum_ends_at)]
-	pub type ReferendumEndsAt<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+	#[pallet::getter(fn delegated_ballot_of)]
+	pub type DelegatedBallots<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ReferendumIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		T::AccountId,
+	>;
 
-	/// Is there a referendum active right now?
+	/// Balance each voter has locked for conviction votes and the block it can be unlocked at
 	#[pallet::storage]
-	#[pallet::getter(fn active_referendum)]
-	pub type ActiveReferendum<T: Config> = StorageValue<_, ()>;
+	#[pallet::getter(fn conviction_lock_of)]
+	pub type ConvictionLocks<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ConvictionLockOf<T>>;
 
 	/// Defines the set of all votes.
 	/// Users need to call `register_voter` to end up here.
-	/// Each voter has T::MaxVotes ** 2 points and only has less when he voted on a referendum.
-	/// Points are returned once the referendum ends.
+	/// Each voter is granted `T::InitialPoints` and only has less while a ballot of theirs is
+	/// waiting for a refund in `PendingRefunds`.
 	#[pallet::storage]
 	#[pallet::getter(fn voter_points)]
 	pub type VoterPoints<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Points>;
 
+	/// Number of registered voters, which sets the electorate of referenda
+	#[pallet::storage]
+	#[pallet::getter(fn voter_count)]
+	pub type VoterCount<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Origin of the calls enacted by this pallet
+	#[pallet::origin]
+	#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub enum Origin {
+		/// The call was approved as a proposal of a referendum
+		Approved(ReferendumIndex, ProposalIndex),
+	}
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub _phantom: sp_std::marker::PhantomData<T>,
@@ -148,14 +461,61 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		/// A voter has been registered successfully
 		VoterRegistered,
+		/// A voter has been deregistered, its ballots in active referenda retracted
+		VoterDeregistered(T::AccountId),
+		/// The ballots and full results of a referendum past its retention period were removed,
+		/// keeping a summary of its results
+		ReferendumPruned(ReferendumIndex),
 		/// A proposal was successfully submitted
-		ProposalSubmitted(Proposal<T>, T::AccountId),
+		ProposalSubmitted(T::Hash, T::AccountId),
+		/// A voter seconded a queued proposal
+		Seconded(T::Hash, T::AccountId),
+		/// A proposal was dropped from the queue after waiting for `T::ProposalLifetime` blocks
+		ProposalExpired(T::Hash),
 		/// A vote was successfully submitted
-		VoteSubmitted(ReferendumVotes<T>, T::AccountId),
+		VoteSubmitted(ReferendumIndex, ReferendumVotes<T>, T::AccountId),
+		/// A voter replaced their ballot with a new one
+		VotesChanged(ReferendumIndex, ReferendumVotes<T>, T::AccountId),
+		/// A voter retracted their ballot
+		VotesRemoved(ReferendumIndex, T::AccountId),
+		/// An expired conviction lock was removed
+		Unlocked(T::AccountId, BalanceOf<T>),
+		/// A voter delegated their points to another voter
+		Delegated(T::AccountId, T::AccountId),
+		/// A voter revoked their delegation
+		Undelegated(T::AccountId),
 		/// Started a referendum
-		ReferendumStarted(ReferendumIndex, Vec<Proposal<T>>),
+		ReferendumStarted(ReferendumIndex, Vec<T::Hash>),
 		/// Referendum ended
 		ReferendumEnded(ReferendumIndex, Vec<ProposalInfo<T::Hash, T::BlockNumber>>),
+		/// A referendum was due to start in a block where another one ended, it will start at
+		/// the given block instead
+		ReferendumScheduled(ReferendumIndex, T::BlockNumber),
+		/// A referendum could not start, if it was scheduled it is cancelled
+		ReferendumLaunchFailed(ReferendumIndex, DispatchError),
+		/// A referendum could not move on to its next stage and was cancelled
+		LifecycleError(ReferendumIndex, DispatchError),
+		/// An active referendum was cancelled, its points and deposits refunded
+		ReferendumCancelled(ReferendumIndex),
+		/// A proposal was vetoed and can't be submitted again until the given block
+		ProposalVetoed(ReferendumIndex, T::Hash, T::BlockNumber),
+		/// The call of an approved proposal will be dispatched at the given block
+		EnactmentScheduled(ReferendumIndex, ProposalIndex, T::BlockNumber),
+		/// The call of an approved proposal was dispatched
+		ProposalEnacted(ReferendumIndex, ProposalIndex, DispatchResult),
+		/// The call of an approved proposal did not fit in the block and was postponed to the given
+		/// block
+		EnactmentPostponed(ReferendumIndex, ProposalIndex, T::BlockNumber),
+		/// A queued proposal was removed as spam and its deposit slashed
+		ProposalSlashed(T::Hash, T::AccountId, BalanceOf<T>),
+		/// A proposer withdrew their queued proposal
+		ProposalWithdrawn(T::Hash, T::AccountId),
+		/// A moderator removed a queued proposal, returning its deposits
+		ProposalRemoved(T::Hash),
+		/// The text of a proposal was stored on-chain
+		PreimageNoted(T::Hash, T::AccountId),
+		/// The text of a proposal was removed from storage
+		PreimageCleared(T::Hash),
 	}
 
 	#[pallet::error]
@@ -164,8 +524,10 @@ pub mod pallet {
 		ProposalTooLong,
 		/// Proposal queue is full
 		ProposalQueueFull,
-		/// No active referendum right now
+		/// The referendum is not active right now
 		NoActiveReferendum,
+		/// Tried to start a referendum but `T::MaxActiveReferenda` are already running
+		TooManyActiveReferenda,
 		/// Overflow error
 		Overflow,
 		/// Tried to start a referendum but there were no proposals in the queue
@@ -182,35 +544,132 @@ pub mod pallet {
 		AlreadyVoted,
 		/// User does not have an identity
 		NoIdentity,
-		/// Missing votes for proposals in the referendum
-		MissingVotes,
+		/// The ballot has no votes
+		EmptyBallot,
+		/// The ballot votes on a proposal that is not part of the referendum
+		ProposalNotInReferendum,
+		/// The ballot votes on the same proposal more than once
+		DuplicateVote,
+		/// There is no proposal with that hash in the queue
+		ProposalNotInQueue,
+		/// A proposal with the same hash is already in the queue
+		ProposalAlreadyQueued,
+		/// The preimage of the proposal has already been noted
+		PreimageAlreadyNoted,
+		/// The preimage of the proposal has not been noted
+		PreimageMissing,
+		/// The preimage is still referred to by a queued proposal or an ongoing referendum
+		PreimageInUse,
+		/// Only the account that noted the preimage can clear it
+		NotPreimageProvider,
+		/// Voter has not submitted a ballot on the referendum
+		NotVoted,
+		/// Votes with conviction need to lock some balance
+		NoConvictionBalance,
+		/// Not enough free balance to lock for conviction
+		InsufficientFunds,
+		/// The balance locked for conviction is below `T::MinConvictionBalance` for each vote
+		ConvictionBalanceTooLow,
+		/// The account has no conviction lock
+		NotLocked,
+		/// The conviction lock has not expired yet
+		LockNotExpired,
+		/// Voters can't delegate to themselves
+		CannotDelegateToSelf,
+		/// The voter is already delegating, they need to undelegate first
+		AlreadyDelegating,
+		/// The voter is not delegating
+		NotDelegating,
+		/// The delegate already has `T::MaxDelegators` delegators
+		TooManyDelegators,
+		/// The preimage of the proposal is not an encoded call
+		InvalidCall,
+		/// The call takes more than `T::MaxEnactmentWeight` to dispatch
+		CallTooHeavy,
+		/// The voter already seconded the proposal
+		AlreadySeconded,
+		/// The proposal already has `T::MaxSeconds` seconds
+		TooManySeconds,
+		/// Only the account that submitted the proposal can withdraw it
+		NotProposer,
+		/// The proposal was vetoed and is in its cooldown period
+		ProposalBlacklisted,
+		/// The witness data given is lower than the actual value
+		InvalidWitness,
+		/// A proposal of the referendum is not ongoing
+		ProposalNotOngoing,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
-			let mut weight = 0;
-
-			let referendum_ends_at = ReferendumEndsAt::<T>::get();
-
-			if block_number == referendum_ends_at {
-				let _ = Self::end_referendum(); // TODO: Deal with error
+			let active_referenda = ActiveReferenda::<T>::get();
+			let mut weight = T::DbWeight::get().reads(1 + 2 * active_referenda.len() as Weight);
+			weight.saturating_accrue(Self::expire_blacklist(block_number));
+
+			// Referenda are ended first, so that none starts in a block where another one ends
+			let mut ended = false;
+			for referendum_index in active_referenda.iter().copied() {
+				if let Some(ReferendumStatus::Voting { ends_at }) =
+					ReferendumStatusOf::<T>::get(referendum_index)
+				{
+					if block_number >= ends_at {
+						weight.saturating_accrue(Self::close_referendum(referendum_index));
+						ended = true;
+					}
+				}
+			}
 
-				weight += 5_000 * T::ProposalsPerReferendum::get();
+			// Calls approved by the referenda that just ended are also due now if there is no
+			// enactment period
+			weight.saturating_accrue(Self::enact_due(block_number, weight));
+
+			if !ended {
+				for referendum_index in active_referenda {
+					if let Some(ReferendumStatus::Scheduled { starts_at }) =
+						ReferendumStatusOf::<T>::get(referendum_index)
+					{
+						if block_number >= starts_at {
+							weight.saturating_accrue(Self::launch_scheduled_referendum(
+								referendum_index,
+								block_number,
+							));
+						}
+					}
+				}
 			}
 
 			if (block_number % T::LaunchPeriod::get()).is_zero() {
-				let _ = Self::start_referendum(block_number); // TODO: Deal with error
-				weight += 10_000 * T::ProposalsPerReferendum::get();
+				weight.saturating_accrue(Self::expire_proposals(block_number));
+				let referendum_index = Self::referendum_count();
+				let launched = if ended {
+					Self::schedule_referendum(block_number.saturating_add(One::one()))
+				} else {
+					Self::start_referendum(referendum_index, block_number)
+				};
+				if let Err(error) = launched {
+					// Not enough proposals only means there is nothing to vote on
+					if error != Error::<T>::NotEnoughProposalsInQueue.into() {
+						Self::deposit_event(Event::<T>::ReferendumLaunchFailed(
+							referendum_index,
+							error,
+						));
+					}
+				}
+				weight = weight.saturating_add(T::WeightInfo::start_referendum());
 			}
 
-			weight.into()
+			weight
+		}
+
+		fn on_idle(block_number: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::prune_referendum(block_number, remaining_weight)
 		}
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(T::WeightInfo::submit_proposal(raw_proposal.len() as u32))]
 		pub fn submit_proposal(origin: OriginFor<T>, raw_proposal: Vec<u8>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
@@ -218,184 +677,1618 @@ pub mod pallet {
 
 			let proposal: Proposal<T> =
 				raw_proposal.try_into().map_err(|()| Error::<T>::ProposalTooLong)?;
+			let proposal_hash = Self::hash_of(&proposal);
 
-			QueuedProposals::<T>::try_append(proposal.clone())
-				.map_err(|()| Error::<T>::ProposalQueueFull)?;
+			if !ProposalPreimages::<T>::contains_key(proposal_hash) {
+				// The proposal deposit already pays for the bytes of the preimage
+				Self::do_note_preimage(proposal, who.clone(), Zero::zero());
+			}
 
-			Self::deposit_event(Event::ProposalSubmitted(proposal, who));
+			Self::do_submit_proposal(proposal_hash, who, ProposalKind::Text)
+		}
 
-			Ok(())
+		/// Submit a proposal whose preimage has already been noted with `note_preimage`
+		#[pallet::weight(T::WeightInfo::submit_proposal_hash())]
+		pub fn submit_proposal_hash(
+			origin: OriginFor<T>,
+			proposal_hash: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::is_a_voter(&who), Error::<T>::NotAVoter);
+			ensure!(
+				ProposalPreimages::<T>::contains_key(proposal_hash),
+				Error::<T>::PreimageMissing
+			);
+
+			Self::do_submit_proposal(proposal_hash, who, ProposalKind::Text)
+		}
+
+		/// Submit a call to be dispatched with `Origin::Approved` if the proposal is approved
+		#[pallet::weight(T::WeightInfo::submit_call_proposal(call.encoded_size() as u32))]
+		pub fn submit_call_proposal(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::Call>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_submit_call_proposal(who, &call, ProposalKind::Call)
+		}
+
+		/// Submit a call whose encoding has already been noted with `note_preimage`
+		#[pallet::weight(T::WeightInfo::submit_call_proposal_hash())]
+		pub fn submit_call_proposal_hash(
+			origin: OriginFor<T>,
+			proposal_hash: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_submit_call_proposal_hash(who, proposal_hash, ProposalKind::Call)
+		}
+
+		/// Submit a call to be dispatched with the root origin if the proposal is approved under
+		/// `T::RootCallApprovalRule`
+		#[pallet::weight(T::WeightInfo::submit_call_proposal(call.encoded_size() as u32))]
+		pub fn submit_root_call_proposal(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::Call>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_submit_call_proposal(who, &call, ProposalKind::RootCall)
+		}
+
+		/// Submit a root call whose encoding has already been noted with `note_preimage`, such as
+		/// a runtime upgrade too large to be submitted directly
+		#[pallet::weight(T::WeightInfo::submit_call_proposal_hash())]
+		pub fn submit_root_call_proposal_hash(
+			origin: OriginFor<T>,
+			proposal_hash: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_submit_call_proposal_hash(who, proposal_hash, ProposalKind::RootCall)
 		}
 
-		/// Submit votes distributed amongst all proposals in the current referendum
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1) + T::DbWeight::get().reads(1))]
-		pub fn submit_votes(origin: OriginFor<T>, all_votes: ReferendumVotes<T>) -> DispatchResult {
+		/// Second a queued proposal, reserving `T::SecondDeposit` until it leaves the queue.
+		/// Referenda pick the most seconded proposals first.
+		#[pallet::weight(T::WeightInfo::second())]
+		pub fn second(origin: OriginFor<T>, proposal_hash: T::Hash) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
 			ensure!(Self::is_a_voter(&who), Error::<T>::NotAVoter);
-			ensure!(ActiveReferendum::<T>::exists(), Error::<T>::NoActiveReferendum);
-			ensure!(!Self::already_voted(&who), Error::<T>::AlreadyVoted);
+
+			let mut queued_proposals = Self::queued_proposals();
+			let queued_proposal = queued_proposals
+				.iter_mut()
+				.find(|queued_proposal| queued_proposal.proposal_hash == proposal_hash)
+				.ok_or(Error::<T>::ProposalNotInQueue)?;
 			ensure!(
-				all_votes.len() as u32 == T::ProposalsPerReferendum::get(),
-				Error::<T>::MissingVotes
+				!SecondsOf::<T>::contains_key(proposal_hash, &who),
+				Error::<T>::AlreadySeconded
 			);
+			ensure!(queued_proposal.seconds < T::MaxSeconds::get(), Error::<T>::TooManySeconds);
 
-			let intended_votes = all_votes.iter().map(|(amount, _)| amount).sum::<u32>();
-
-			ensure!(Self::has_enough_points(&who, intended_votes), Error::<T>::NotEnoughPoints);
-
-			let current_referendum = ReferendumCount::<T>::get();
-
-			for (proposal_index, votes) in all_votes.iter().enumerate() {
-				let maybe_info =
-					ReferendumInfo::<T>::get(current_referendum, proposal_index as u32);
-				let mut proposal_info =
-					maybe_info.expect("We already checked current referendum exists; qed");
-				match proposal_info {
-					ProposalInfo::Finished(_) =>
-						panic!("We already checked current referendum exists; qed"),
-					ProposalInfo::Ongoing(ref mut ongoing_info) => match &votes.1 {
-						Vote::Aye => {
-							let aye_votes = ongoing_info
-								.tally
-								.aye_votes
-								.checked_add(votes.0)
-								.ok_or(Error::<T>::Overflow)?;
-							ongoing_info.tally.aye_votes = aye_votes;
-						},
-						Vote::Nay => {
-							let nay_votes = ongoing_info
-								.tally
-								.nay_votes
-								.checked_add(votes.0)
-								.ok_or(Error::<T>::Overflow)?;
-							ongoing_info.tally.nay_votes = nay_votes;
-						},
-					},
-				};
-				// TODO: Optimize, update points only in the end
-				Self::take_points_from_voter(&who, votes.0);
-				ReferendumInfo::<T>::insert(
-					current_referendum,
-					proposal_index as u32,
-					proposal_info,
-				);
-			}
+			let deposit = T::SecondDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			queued_proposal.seconds.saturating_inc();
+			SecondsOf::<T>::insert(proposal_hash, &who, deposit);
 
-			Self::deposit_event(Event::VoteSubmitted(all_votes, who));
+			QueuedProposals::<T>::put(queued_proposals);
+
+			Self::deposit_event(Event::Seconded(proposal_hash, who));
 
 			Ok(())
 		}
 
-		#[pallet::weight(10_000)]
-		pub fn register_voter(_: OriginFor<T>, account: T::AccountId) -> DispatchResult {
-			Self::do_register_voter(account)
+		/// Store the text of a proposal on-chain, reserving a deposit for each byte
+		#[pallet::weight(T::WeightInfo::note_preimage(raw_proposal.len() as u32))]
+		pub fn note_preimage(origin: OriginFor<T>, raw_proposal: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::is_a_voter(&who), Error::<T>::NotAVoter);
+
+			let proposal: Proposal<T> =
+				raw_proposal.try_into().map_err(|()| Error::<T>::ProposalTooLong)?;
+			ensure!(
+				!ProposalPreimages::<T>::contains_key(Self::hash_of(&proposal)),
+				Error::<T>::PreimageAlreadyNoted
+			);
+
+			let deposit =
+				T::PreimageByteDeposit::get().saturating_mul((proposal.len() as u32).into());
+			T::Currency::reserve(&who, deposit)?;
+
+			Self::do_note_preimage(proposal, who, deposit);
+
+			Ok(())
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	fn do_register_voter(account: T::AccountId) -> DispatchResult {
-		ensure!(T::Identity::has_identity(&account), Error::<T>::NoIdentity);
-		ensure!(VoterPoints::<T>::get(&account) == None, Error::<T>::VoterAlreadyRegistered);
-		VoterPoints::<T>::insert(account, 100);
-		Ok(())
-	}
+		/// Remove a preimage that no queued proposal or ongoing referendum refers to,
+		/// returning its deposit
+		#[pallet::weight(T::WeightInfo::clear_preimage())]
+		pub fn clear_preimage(origin: OriginFor<T>, proposal_hash: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-	fn start_referendum(block_number: T::BlockNumber) -> DispatchResult {
-		let referendum_index = Self::referendum_count();
+			let preimage =
+				ProposalPreimages::<T>::get(proposal_hash).ok_or(Error::<T>::PreimageMissing)?;
+			ensure!(preimage.provider == who, Error::<T>::NotPreimageProvider);
+			ensure!(preimage.references == 0, Error::<T>::PreimageInUse);
 
-		let mut queued_proposals = Self::queued_proposals();
+			Self::do_clear_preimage(proposal_hash, preimage);
 
-		ensure!(
-			queued_proposals.len() >= T::ProposalsPerReferendum::get() as usize,
-			Error::<T>::NotEnoughProposalsInQueue
-		);
+			Ok(())
+		}
 
-		let mut proposal_texts = Vec::new();
+		/// Submit votes on any of the proposals of an active referendum, keyed by their index.
+		/// The weight of the delegators it isn't cast for is refunded.
+		#[pallet::weight(
+			T::WeightInfo::submit_votes(all_votes.len() as u32, T::MaxDelegators::get())
+		)]
+		pub fn submit_votes(
+			origin: OriginFor<T>,
+			referendum_index: ReferendumIndex,
+			all_votes: ReferendumVotes<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let votes = all_votes.len() as u32;
+			let delegators = Self::do_submit_votes(
+				who,
+				referendum_index,
+				all_votes,
+				Conviction::None,
+				Zero::zero(),
+			)?;
+
+			Ok(Some(T::WeightInfo::submit_votes(votes, delegators)).into())
+		}
 
-		queued_proposals
-			.drain(0..T::ProposalsPerReferendum::get() as usize)
-			.map(|proposal_text| {
-				proposal_texts.push(proposal_text.clone());
-				<<T as frame_system::Config>::Hashing as Hasher>::hash(&proposal_text)
-			})
-			.enumerate()
-			.for_each(|(index, proposal_hash)| {
-				let ongoing_proposal_info =
-					OngoingProposalInfo { proposal_hash, tally: Default::default() };
-				let proposal_info = ProposalInfo::Ongoing(ongoing_proposal_info);
+		/// Submit votes locking `balance` with `conviction`, which multiplies every vote of the
+		/// ballot. The balance stays locked for the conviction periods after the referendum ends,
+		/// and has to be at least `T::MinConvictionBalance` for each vote.
+		/// The weight of the delegators it isn't cast for is refunded.
+		#[pallet::weight(T::WeightInfo::submit_votes_with_conviction(
+			all_votes.len() as u32,
+			T::MaxDelegators::get(),
+		))]
+		pub fn submit_votes_with_conviction(
+			origin: OriginFor<T>,
+			referendum_index: ReferendumIndex,
+			all_votes: ReferendumVotes<T>,
+			conviction: Conviction,
+			balance: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let votes = all_votes.len() as u32;
+			let delegators =
+				Self::do_submit_votes(who, referendum_index, all_votes, conviction, balance)?;
 
-				ReferendumInfo::<T>::insert(
-					referendum_index,
-					index as ProposalIndex,
-					proposal_info,
+			Ok(Some(T::WeightInfo::submit_votes_with_conviction(votes, delegators)).into())
+		}
+
+		/// Replace the ballot submitted on an active referendum with a new one.
+		/// Changing a ballot cast by a delegate overrides it with a direct one.
+		/// It is charged for an old ballot voting on every proposal, replaced for the most
+		/// delegators, and the weight of what was not replaced is refunded.
+		#[pallet::weight(T::WeightInfo::change_votes(
+			all_votes.len() as u32,
+			T::ProposalsPerReferendum::get(),
+			T::MaxDelegators::get(),
+		))]
+		pub fn change_votes(
+			origin: OriginFor<T>,
+			referendum_index: ReferendumIndex,
+			all_votes: ReferendumVotes<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::is_active(referendum_index), Error::<T>::NoActiveReferendum);
+			let old_ballot =
+				VotingOf::<T>::get(referendum_index, &who).ok_or(Error::<T>::NotVoted)?;
+			let old_votes = old_ballot.votes.len() as u32;
+
+			Self::reclaim_points(&who);
+			let cost = Self::checked_ballot_cost(&all_votes)?;
+			let points = VoterPoints::<T>::get(&who).ok_or(Error::<T>::NotAVoter)?;
+			let remaining_points = points
+				.saturating_add(old_ballot.points)
+				.checked_sub(cost)
+				.ok_or(Error::<T>::NotEnoughPoints)?;
+
+			// The new ballot keeps the conviction of the old one, which has to cover its votes
+			let conviction = old_ballot.conviction;
+			if conviction != Conviction::None {
+				ensure!(
+					old_ballot.balance >= Self::min_conviction_balance(&all_votes),
+					Error::<T>::ConvictionBalanceTooLow
 				);
+			}
+			Self::update_tallies(
+				referendum_index,
+				&old_ballot.votes,
+				conviction,
+				u32::checked_sub,
+			)?;
+			Self::update_tallies(referendum_index, &all_votes, conviction, u32::checked_add)?;
+
+			VoterPoints::<T>::insert(&who, remaining_points);
+			VotingOf::<T>::insert(
+				referendum_index,
+				&who,
+				Ballot { votes: all_votes.clone(), points: cost, ..old_ballot },
+			);
+			DelegatedBallots::<T>::remove(referendum_index, &who);
+
+			Self::remove_delegated_ballots(&who, referendum_index)?;
+			let delegators = Self::apply_delegated_ballots(&who, referendum_index, &all_votes)?;
+
+			let votes = all_votes.len() as u32;
+			Self::deposit_event(Event::VotesChanged(referendum_index, all_votes, who));
+
+			Ok(Some(T::WeightInfo::change_votes(votes, old_votes, delegators)).into())
+		}
+
+		/// Retract the ballot submitted on an active referendum, getting its points back.
+		/// The ballots cast on behalf of the voter's delegators are retracted as well.
+		/// A conviction lock already taken is not shortened.
+		#[pallet::weight(T::WeightInfo::remove_votes(T::MaxDelegators::get()))]
+		pub fn remove_votes(
+			origin: OriginFor<T>,
+			referendum_index: ReferendumIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::is_active(referendum_index), Error::<T>::NoActiveReferendum);
+			ensure!(Self::already_voted(referendum_index, &who), Error::<T>::NotVoted);
+
+			Self::undo_ballot(referendum_index, &who)?;
+			Self::remove_delegated_ballots(&who, referendum_index)?;
+
+			Self::deposit_event(Event::VotesRemoved(referendum_index, who));
+
+			Ok(())
+		}
+
+		/// Let `delegate` vote with the caller's points on every referendum the caller doesn't
+		/// vote on directly, starting with the ballots the delegate already cast on active
+		/// referenda. Delegated ballots are not passed on to the delegate's own delegate.
+		#[pallet::weight(T::WeightInfo::delegate())]
+		pub fn delegate(origin: OriginFor<T>, delegate: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::is_a_voter(&who), Error::<T>::NotAVoter);
+			ensure!(Self::is_a_voter(&delegate), Error::<T>::NotAVoter);
+			ensure!(who != delegate, Error::<T>::CannotDelegateToSelf);
+			ensure!(!Delegations::<T>::contains_key(&who), Error::<T>::AlreadyDelegating);
+
+			Delegators::<T>::try_append(&delegate, who.clone())
+				.map_err(|()| Error::<T>::TooManyDelegators)?;
+			Delegations::<T>::insert(&who, &delegate);
+
+			// Ballots the delegate cast directly on active referenda apply right away
+			for referendum_index in Self::active_referenda() {
+				if !Self::is_active(referendum_index) ||
+					DelegatedBallots::<T>::contains_key(referendum_index, &delegate)
+				{
+					continue;
+				}
+				if let Some(ballot) = VotingOf::<T>::get(referendum_index, &delegate) {
+					let cost = Self::checked_ballot_cost(&ballot.votes)?;
+					Self::apply_delegated_ballot(
+						&delegate,
+						&who,
+						referendum_index,
+						&ballot.votes,
+						cost,
+					)?;
+				}
+			}
+
+			Self::deposit_event(Event::Delegated(who, delegate));
+
+			Ok(())
+		}
+
+		/// Revoke the caller's delegation, retracting the ballots the delegate cast on their
+		/// behalf in active referenda
+		#[pallet::weight(T::WeightInfo::undelegate())]
+		pub fn undelegate(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let delegate = Delegations::<T>::take(&who).ok_or(Error::<T>::NotDelegating)?;
+			Delegators::<T>::mutate(&delegate, |delegators| {
+				delegators.retain(|delegator| *delegator != who)
 			});
+			for referendum_index in Self::active_referenda() {
+				if DelegatedBallots::<T>::contains_key(referendum_index, &who) {
+					Self::undo_ballot(referendum_index, &who)?;
+				}
+			}
 
-		QueuedProposals::<T>::put(queued_proposals);
+			Self::deposit_event(Event::Undelegated(who));
+
+			Ok(())
+		}
 
-		// Update current referendum related variables
-		ReferendumEndsAt::<T>::put(block_number.saturating_add(T::VotingPeriod::get()));
-		ActiveReferendum::<T>::put(());
+		/// Remove the conviction lock of `target` once it has expired
+		#[pallet::weight(T::WeightInfo::unlock())]
+		pub fn unlock(origin: OriginFor<T>, target: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
 
-		Self::deposit_event(Event::<T>::ReferendumStarted(referendum_index, proposal_texts));
+			let lock = ConvictionLocks::<T>::get(&target).ok_or(Error::<T>::NotLocked)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= lock.unlock_at,
+				Error::<T>::LockNotExpired
+			);
 
-		Ok(())
-	}
+			T::Currency::remove_lock(CONVICTION_ID, &target);
+			ConvictionLocks::<T>::remove(&target);
 
-	fn end_referendum() -> DispatchResult {
-		let referendum_index = Self::referendum_count();
-		let end = Self::referendum_ends_at();
+			Self::deposit_event(Event::Unlocked(target, lock.balance));
 
-		let mut finished_proposals = Vec::new();
+			Ok(())
+		}
 
-		for proposal_index in 0..T::ProposalsPerReferendum::get() {
-			let old_proposal_info = ReferendumInfo::<T>::get(referendum_index, proposal_index)
-				.expect("referendum is ending, old proposal exists; qed");
+		/// Register `account`, which needs an identity, as a voter.
+		/// Accounts can register themselves, others need the `RegistrationOrigin`
+		#[pallet::weight(T::WeightInfo::register_voter())]
+		pub fn register_voter(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			Self::ensure_can_register(origin, &account)?;
+
+			Self::do_register_voter(account)
+		}
+
+		/// Remove `account` from the voters, retracting its ballots in active referenda along
+		/// with those it cast on behalf of its delegators, and dropping its delegations.
+		/// Accounts can deregister themselves, others need the `RegistrationOrigin`
+		#[pallet::weight(T::WeightInfo::deregister_voter(
+			T::MaxActiveReferenda::get(),
+			T::MaxDelegators::get()
+		))]
+		pub fn deregister_voter(
+			origin: OriginFor<T>,
+			account: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_can_register(origin, &account)?;
+
+			let weight = Self::do_deregister_voter(&account)?;
+			Ok(Some(weight).into())
+		}
+
+		/// Cancel an active referendum, refunding the points of its ballots and the deposits of
+		/// its proposals.
+		/// `voters_witness` is an upper bound on the voters of the referendum.
+		#[pallet::weight(T::WeightInfo::cancel_referendum(*voters_witness))]
+		pub fn cancel_referendum(
+			origin: OriginFor<T>,
+			referendum_index: ReferendumIndex,
+			voters_witness: u32,
+		) -> DispatchResultWithPostInfo {
+			T::CancellationOrigin::ensure_origin(origin)?;
+
+			let voters = Self::do_cancel_referendum(referendum_index)?;
+			ensure!(voters <= voters_witness, Error::<T>::InvalidWitness);
+
+			Ok(Some(T::WeightInfo::cancel_referendum(voters)).into())
+		}
 
-			let approved = match old_proposal_info {
-				ProposalInfo::Ongoing(ongoing_proposal_info) =>
-					ongoing_proposal_info.tally.result(),
-				ProposalInfo::Finished(_) => panic!("Old proposal has to be ongoing; qed"),
+		/// Veto a proposal of an active referendum, cancelling the referendum and keeping the
+		/// proposal from being submitted again for `T::CooldownPeriod` blocks.
+		/// `voters_witness` is an upper bound on the voters of the referendum.
+		#[pallet::weight(T::WeightInfo::veto(*voters_witness))]
+		pub fn veto(
+			origin: OriginFor<T>,
+			referendum_index: ReferendumIndex,
+			proposal_index: ProposalIndex,
+			voters_witness: u32,
+		) -> DispatchResultWithPostInfo {
+			T::VetoOrigin::ensure_origin(origin)?;
+
+			let proposal_hash = match ReferendumInfo::<T>::get(referendum_index, proposal_index) {
+				Some(ProposalInfo::Ongoing(ongoing_proposal_info)) =>
+					ongoing_proposal_info.proposal_hash,
+				_ => return Err(Error::<T>::NoActiveReferendum.into()),
 			};
+			let voters = Self::do_cancel_referendum(referendum_index)?;
+			ensure!(voters <= voters_witness, Error::<T>::InvalidWitness);
 
-			let new_proposal_info = ProposalInfo::Finished(FinishedProposalInfo { approved, end });
+			let until =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::CooldownPeriod::get());
+			Blacklist::<T>::insert(proposal_hash, until);
+			BlacklistExpiries::<T>::insert(until, proposal_hash, ());
 
-			finished_proposals.push(new_proposal_info.clone());
+			Self::deposit_event(Event::ProposalVetoed(referendum_index, proposal_hash, until));
 
-			ReferendumInfo::<T>::insert(referendum_index, proposal_index, new_proposal_info);
+			Ok(Some(T::WeightInfo::veto(voters)).into())
 		}
 
-		// Update active referendum related variables
-		ActiveReferendum::<T>::kill();
-		ReferendumCount::<T>::put(referendum_index + 1);
+		/// Remove a spam proposal from the queue, slashing the proposer's deposit.
+		/// Voters that seconded it get their deposits back.
+		#[pallet::weight(T::WeightInfo::slash_proposal())]
+		pub fn slash_proposal(origin: OriginFor<T>, proposal_hash: T::Hash) -> DispatchResult {
+			T::ModeratorOrigin::ensure_origin(origin)?;
 
-		Self::deposit_event(Event::<T>::ReferendumEnded(referendum_index, finished_proposals));
+			let QueuedProposal { proposer, deposit, .. } =
+				Self::take_queued_proposal(proposal_hash)?;
+			Self::release_preimage(proposal_hash);
+			Self::refund_seconds(proposal_hash);
 
-		Ok(())
-	}
+			let (_, unslashed) = T::Currency::slash_reserved(&proposer, deposit);
+			let slashed = deposit.saturating_sub(unslashed);
 
-	fn is_a_voter(account: &T::AccountId) -> bool {
-		VoterPoints::<T>::get(account).is_some()
-	}
+			Self::deposit_event(Event::ProposalSlashed(proposal_hash, proposer, slashed));
 
-	fn has_enough_points(account: &T::AccountId, intended_votes: u32) -> bool {
-		let points_available =
-			VoterPoints::<T>::get(account).expect("check should be done outside this function");
-		points_available >= intended_votes.pow(2)
-	}
+			Ok(())
+		}
+
+		/// Withdraw a proposal the caller submitted from the queue, getting its deposit back
+		#[pallet::weight(T::WeightInfo::withdraw_proposal())]
+		pub fn withdraw_proposal(origin: OriginFor<T>, proposal_hash: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-	fn take_points_from_voter(account: &T::AccountId, votes: u32) {
-		let available_points =
-			VoterPoints::<T>::get(account).expect("check should be done outside this function");
-		// `has_enough_points` should've been called already
-		VoterPoints::<T>::insert(account, available_points - votes.pow(2));
+			let queued_proposals = Self::queued_proposals();
+			let queue_index = Self::queue_index_of(&queued_proposals, proposal_hash)?;
+			ensure!(queued_proposals[queue_index].proposer == who, Error::<T>::NotProposer);
+
+			let queued_proposal = Self::take_queued_proposal(proposal_hash)?;
+			Self::drop_queued_proposal(queued_proposal);
+
+			Self::deposit_event(Event::ProposalWithdrawn(proposal_hash, who));
+
+			Ok(())
+		}
+
+		/// Remove a proposal from the queue without punishing the proposer, such as a duplicate,
+		/// returning every deposit held for it
+		#[pallet::weight(T::WeightInfo::remove_proposal())]
+		pub fn remove_proposal(origin: OriginFor<T>, proposal_hash: T::Hash) -> DispatchResult {
+			T::ModeratorOrigin::ensure_origin(origin)?;
+
+			let queued_proposal = Self::take_queued_proposal(proposal_hash)?;
+			Self::drop_queued_proposal(queued_proposal);
+
+			Self::deposit_event(Event::ProposalRemoved(proposal_hash));
+
+			Ok(())
+		}
+	}
+}
+
+/// Ensures the origin is a call enacted from a proposal approved in a referendum,
+/// returning the referendum and the position of the proposal in it
+pub struct EnsureQuadraticMajority;
+impl<O: Into<Result<Origin, O>> + From<Origin>> EnsureOrigin<O> for EnsureQuadraticMajority {
+	type Success = (ReferendumIndex, ProposalIndex);
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().map(|Origin::Approved(referendum_index, proposal_index)| {
+			(referendum_index, proposal_index)
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_origin() -> O {
+		O::from(Origin::Approved(0, 0))
+	}
+}
+
+/// Voters whose identity is cleared are deregistered: their ballots in active referenda, along
+/// with those cast on behalf of their delegators, are retracted and their delegations dropped
+impl<T: Config> OnIdentityChanged<T::AccountId> for Pallet<T> {
+	fn on_identity_cleared(who: &T::AccountId) {
+		if Self::is_a_voter(who) && with_storage_layer(|| Self::do_deregister_voter(who)).is_err() {
+			frame_support::defensive!("voter without an identity could not be deregistered");
+		}
+	}
+
+	/// A voter that voted on every active referendum on behalf of the most delegators
+	fn max_weight() -> Weight {
+		T::WeightInfo::deregister_voter(T::MaxActiveReferenda::get(), T::MaxDelegators::get())
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn do_register_voter(account: T::AccountId) -> DispatchResult {
+		ensure!(T::Identity::has_identity(&account), Error::<T>::NoIdentity);
+		ensure!(VoterPoints::<T>::get(&account) == None, Error::<T>::VoterAlreadyRegistered);
+		VoterPoints::<T>::insert(account, T::InitialPoints::get());
+		VoterCount::<T>::mutate(|count| count.saturating_inc());
+		Ok(())
+	}
+
+	/// Returns the weight it took, given the referenda `account` voted on and its delegators
+	fn do_deregister_voter(account: &T::AccountId) -> Result<Weight, DispatchError> {
+		ensure!(Self::is_a_voter(account), Error::<T>::NotAVoter);
+
+		let mut voted: u32 = 0;
+		for referendum_index in Self::active_referenda() {
+			if Self::already_voted(referendum_index, account) {
+				Self::undo_ballot(referendum_index, account)?;
+				Self::remove_delegated_ballots(account, referendum_index)?;
+				voted.saturating_inc();
+			}
+		}
+		if let Some(delegate) = Delegations::<T>::take(account) {
+			Delegators::<T>::mutate(&delegate, |delegators| {
+				delegators.retain(|delegator| delegator != account)
+			});
+		}
+		let delegators = Delegators::<T>::take(account);
+		for delegator in delegators.iter() {
+			Delegations::<T>::remove(delegator);
+		}
+
+		// Points of ballots on past referenda are no longer owed
+		PendingRefunds::<T>::remove(account);
+		VoterPoints::<T>::remove(account);
+		VoterCount::<T>::mutate(|count| count.saturating_dec());
+
+		Self::deposit_event(Event::VoterDeregistered(account.clone()));
+
+		Ok(T::WeightInfo::deregister_voter(voted, delegators.len() as u32))
+	}
+
+	/// Accounts can (de)register themselves, others need the `RegistrationOrigin`
+	fn ensure_can_register(origin: OriginFor<T>, account: &T::AccountId) -> DispatchResult {
+		match T::RegistrationOrigin::try_origin(origin) {
+			Ok(_) => Ok(()),
+			Err(origin) => {
+				let who = ensure_signed(origin)?;
+				ensure!(who == *account, DispatchError::BadOrigin);
+				Ok(())
+			},
+		}
+	}
+
+	/// Returns the number of delegators the ballot was cast for, to charge for
+	fn do_submit_votes(
+		who: T::AccountId,
+		referendum_index: ReferendumIndex,
+		all_votes: ReferendumVotes<T>,
+		conviction: Conviction,
+		balance: BalanceOf<T>,
+	) -> Result<u32, DispatchError> {
+		ensure!(Self::is_a_voter(&who), Error::<T>::NotAVoter);
+		ensure!(Self::is_active(referendum_index), Error::<T>::NoActiveReferendum);
+		// Voting directly overrides the ballot cast by a delegate
+		if DelegatedBallots::<T>::contains_key(referendum_index, &who) {
+			Self::undo_ballot(referendum_index, &who)?;
+		}
+		ensure!(!Self::already_voted(referendum_index, &who), Error::<T>::AlreadyVoted);
+
+		Self::reclaim_points(&who);
+		let cost = Self::checked_ballot_cost(&all_votes)?;
+		let points = VoterPoints::<T>::get(&who).ok_or(Error::<T>::NotAVoter)?;
+		let remaining_points = points.checked_sub(cost).ok_or(Error::<T>::NotEnoughPoints)?;
+
+		let balance = if conviction == Conviction::None {
+			Zero::zero()
+		} else {
+			Self::extend_conviction_lock(&who, referendum_index, &all_votes, conviction, balance)?;
+			balance
+		};
+		Self::update_tallies(referendum_index, &all_votes, conviction, u32::checked_add)?;
+
+		VoterPoints::<T>::insert(&who, remaining_points);
+		VotingOf::<T>::insert(
+			referendum_index,
+			&who,
+			Ballot { votes: all_votes.clone(), points: cost, conviction, balance },
+		);
+		// Only ballots on active referenda are left after reclaiming points
+		PendingRefunds::<T>::try_append(&who, referendum_index)
+			.map_err(|()| Error::<T>::TooManyActiveReferenda)?;
+		let delegators = Self::apply_delegated_ballots(&who, referendum_index, &all_votes)?;
+
+		Self::deposit_event(Event::VoteSubmitted(referendum_index, all_votes, who));
+
+		Ok(delegators)
+	}
+
+	/// Cast `votes` on behalf of every delegator of `delegate` that hasn't voted on the
+	/// referendum, each paying the quadratic cost with their own points.
+	/// Delegators without enough points are skipped.
+	/// Returns the number of delegators it went through, to charge for.
+	fn apply_delegated_ballots(
+		delegate: &T::AccountId,
+		referendum_index: ReferendumIndex,
+		votes: &ReferendumVotes<T>,
+	) -> Result<u32, DispatchError> {
+		let cost = Self::checked_ballot_cost(votes)?;
+		let delegators = Self::delegators_of(delegate);
+		for delegator in delegators.iter() {
+			Self::apply_delegated_ballot(delegate, delegator, referendum_index, votes, cost)?;
+		}
+		Ok(delegators.len() as u32)
+	}
+
+	/// Cast `votes`, costing `cost` points, on behalf of `delegator` unless they already voted
+	/// on the referendum or don't have enough points
+	fn apply_delegated_ballot(
+		delegate: &T::AccountId,
+		delegator: &T::AccountId,
+		referendum_index: ReferendumIndex,
+		votes: &ReferendumVotes<T>,
+		cost: Points,
+	) -> DispatchResult {
+		if Self::already_voted(referendum_index, delegator) {
+			return Ok(());
+		}
+		Self::reclaim_points(delegator);
+		let remaining_points =
+			match VoterPoints::<T>::get(delegator).and_then(|points| points.checked_sub(cost)) {
+				Some(remaining_points) => remaining_points,
+				None => return Ok(()),
+			};
+
+		Self::update_tallies(referendum_index, votes, Conviction::None, u32::checked_add)?;
+		VoterPoints::<T>::insert(delegator, remaining_points);
+		VotingOf::<T>::insert(
+			referendum_index,
+			delegator,
+			Ballot {
+				votes: votes.clone(),
+				points: cost,
+				conviction: Conviction::None,
+				balance: Zero::zero(),
+			},
+		);
+		PendingRefunds::<T>::try_append(delegator, referendum_index)
+			.map_err(|()| Error::<T>::TooManyActiveReferenda)?;
+		DelegatedBallots::<T>::insert(referendum_index, delegator, delegate);
+		Ok(())
+	}
+
+	/// Retract the ballots `delegate` cast on behalf of their delegators
+	fn remove_delegated_ballots(
+		delegate: &T::AccountId,
+		referendum_index: ReferendumIndex,
+	) -> DispatchResult {
+		for delegator in Self::delegators_of(delegate) {
+			if DelegatedBallots::<T>::get(referendum_index, &delegator).as_ref() == Some(delegate) {
+				Self::undo_ballot(referendum_index, &delegator)?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Remove the ballot of `who` from the tallies, refunding its points
+	fn undo_ballot(referendum_index: ReferendumIndex, who: &T::AccountId) -> DispatchResult {
+		let ballot = VotingOf::<T>::take(referendum_index, who).ok_or(Error::<T>::NotVoted)?;
+		Self::update_tallies(referendum_index, &ballot.votes, ballot.conviction, u32::checked_sub)?;
+		Self::refund_ballot(referendum_index, who, ballot.points);
+		DelegatedBallots::<T>::remove(referendum_index, who);
+		Ok(())
+	}
+
+	/// Give `who` back the points of their ballot on a referendum, unless they already got them
+	fn refund_ballot(referendum_index: ReferendumIndex, who: &T::AccountId, points: Points) {
+		let refunded = PendingRefunds::<T>::mutate_exists(who, |maybe_pending| {
+			let pending = match maybe_pending {
+				Some(pending) => pending,
+				None => return false,
+			};
+			let len = pending.len();
+			pending.retain(|index| *index != referendum_index);
+			let refunded = pending.len() < len;
+			if pending.is_empty() {
+				*maybe_pending = None;
+			}
+			refunded
+		});
+		if refunded {
+			VoterPoints::<T>::mutate(who, |maybe_points| {
+				if let Some(voter_points) = maybe_points {
+					voter_points.saturating_accrue(points);
+				}
+			});
+		}
+	}
+
+	/// Give `who` back the points of their ballots on referenda that are no longer active
+	fn reclaim_points(who: &T::AccountId) {
+		for referendum_index in PendingRefunds::<T>::get(who).unwrap_or_default() {
+			if !Self::is_active(referendum_index) {
+				let points =
+					VotingOf::<T>::get(referendum_index, who).map_or(0, |ballot| ballot.points);
+				Self::refund_ballot(referendum_index, who, points);
+			}
+		}
+	}
+
+	/// Points `who` gets back the next time they vote
+	fn reclaimable_points(who: &T::AccountId) -> Points {
+		PendingRefunds::<T>::get(who)
+			.unwrap_or_default()
+			.into_iter()
+			.filter(|referendum_index| !Self::is_active(*referendum_index))
+			.filter_map(|referendum_index| VotingOf::<T>::get(referendum_index, who))
+			.fold(0, |points, ballot| points.saturating_add(ballot.points))
+	}
+
+	/// Lock `balance` for `votes` until the conviction periods after the referendum have passed,
+	/// on top of any lock the voter already has
+	fn extend_conviction_lock(
+		who: &T::AccountId,
+		referendum_index: ReferendumIndex,
+		votes: &[(ProposalIndex, u32, Vote)],
+		conviction: Conviction,
+		balance: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(!balance.is_zero(), Error::<T>::NoConvictionBalance);
+		// Otherwise a tiny lock would multiply any number of votes
+		ensure!(
+			balance >= Self::min_conviction_balance(votes),
+			Error::<T>::ConvictionBalanceTooLow
+		);
+		ensure!(balance <= T::Currency::free_balance(who), Error::<T>::InsufficientFunds);
+
+		let ends_at =
+			Self::voting_ends_at(referendum_index).ok_or(Error::<T>::NoActiveReferendum)?;
+		let unlock_at = ends_at.saturating_add(
+			T::ConvictionPeriod::get().saturating_mul(conviction.lock_periods().into()),
+		);
+		let lock = match ConvictionLocks::<T>::get(who) {
+			Some(lock) => ConvictionLock {
+				balance: lock.balance.max(balance),
+				unlock_at: lock.unlock_at.max(unlock_at),
+			},
+			None => ConvictionLock { balance, unlock_at },
+		};
+
+		T::Currency::set_lock(CONVICTION_ID, who, lock.balance, WithdrawReasons::TRANSFER);
+		ConvictionLocks::<T>::insert(who, lock);
+
+		Ok(())
+	}
+
+	/// Start voting on the most seconded queued proposals under `referendum_index`, either the
+	/// next index or the one reserved by a scheduled referendum
+	fn start_referendum(
+		referendum_index: ReferendumIndex,
+		block_number: T::BlockNumber,
+	) -> DispatchResult {
+		let mut queued_proposals = Self::queued_proposals();
+
+		// Expired proposals that haven't been dropped yet can't be picked
+		let mut ranking: Vec<usize> = (0..queued_proposals.len())
+			.filter(|position| block_number < queued_proposals[*position].expires_at)
+			.collect();
+		ensure!(
+			ranking.len() >= T::ProposalsPerReferendum::get() as usize,
+			Error::<T>::NotEnoughProposalsInQueue
+		);
+
+		if !ActiveReferenda::<T>::get().contains(&referendum_index) {
+			ActiveReferenda::<T>::try_append(referendum_index)
+				.map_err(|()| Error::<T>::TooManyActiveReferenda)?;
+		}
+
+		// Most seconded proposals first, the oldest ones first among equals
+		ranking.sort_by_key(|position| Reverse(queued_proposals[*position].seconds));
+		ranking.truncate(T::ProposalsPerReferendum::get() as usize);
+
+		let mut proposal_hashes = Vec::new();
+
+		for (index, position) in ranking.iter().enumerate() {
+			let QueuedProposal { proposal_hash, proposer, deposit, kind, .. } =
+				queued_proposals[*position].clone();
+			Self::refund_seconds(proposal_hash);
+
+			proposal_hashes.push(proposal_hash);
+			ProposalDeposits::<T>::insert(
+				referendum_index,
+				index as ProposalIndex,
+				(proposer, deposit),
+			);
+
+			let ongoing_proposal_info =
+				OngoingProposalInfo { proposal_hash, tally: Default::default(), kind };
+			let proposal_info = ProposalInfo::Ongoing(ongoing_proposal_info);
+
+			ReferendumInfo::<T>::insert(referendum_index, index as ProposalIndex, proposal_info);
+		}
+
+		let mut position = 0;
+		queued_proposals.retain(|_| {
+			let picked = ranking.contains(&position);
+			position += 1;
+			!picked
+		});
+		QueuedProposals::<T>::put(queued_proposals);
+
+		// Update referendum related variables
+		ReferendumStatusOf::<T>::insert(
+			referendum_index,
+			ReferendumStatus::Voting {
+				ends_at: block_number.saturating_add(T::VotingPeriod::get()),
+			},
+		);
+		ReferendumCount::<T>::mutate(|count| *count = (*count).max(referendum_index + 1));
+
+		Self::deposit_event(Event::<T>::ReferendumStarted(referendum_index, proposal_hashes));
+
+		Ok(())
+	}
+
+	/// Reserve the next referendum index for a referendum starting at `starts_at`, as long as
+	/// the queue holds enough proposals for it right now
+	fn schedule_referendum(starts_at: T::BlockNumber) -> DispatchResult {
+		let unexpired = Self::queued_proposals()
+			.iter()
+			.filter(|queued_proposal| starts_at < queued_proposal.expires_at)
+			.count();
+		ensure!(
+			unexpired >= T::ProposalsPerReferendum::get() as usize,
+			Error::<T>::NotEnoughProposalsInQueue
+		);
+
+		let referendum_index = Self::referendum_count();
+		ActiveReferenda::<T>::try_append(referendum_index)
+			.map_err(|()| Error::<T>::TooManyActiveReferenda)?;
+		ReferendumStatusOf::<T>::insert(
+			referendum_index,
+			ReferendumStatus::Scheduled { starts_at },
+		);
+		ReferendumCount::<T>::put(referendum_index + 1);
+
+		Self::deposit_event(Event::<T>::ReferendumScheduled(referendum_index, starts_at));
+
+		Ok(())
+	}
+
+	/// Start a scheduled referendum, cancelling it if it can't start.
+	/// Returns the weight it took.
+	fn launch_scheduled_referendum(
+		referendum_index: ReferendumIndex,
+		block_number: T::BlockNumber,
+	) -> Weight {
+		let weight = T::WeightInfo::start_referendum();
+		match Self::start_referendum(referendum_index, block_number) {
+			Ok(()) => weight,
+			Err(error) => {
+				Self::deposit_event(Event::<T>::ReferendumLaunchFailed(referendum_index, error));
+				weight.saturating_add(Self::abort_referendum(referendum_index))
+			},
+		}
+	}
+
+	/// Tally a referendum whose voting period is over, cancelling it if it can't be tallied.
+	/// Returns the weight it took.
+	fn close_referendum(referendum_index: ReferendumIndex) -> Weight {
+		ReferendumStatusOf::<T>::insert(referendum_index, ReferendumStatus::Tallying);
+
+		match with_storage_layer(|| Self::end_referendum(referendum_index)) {
+			Ok(weight) => weight,
+			Err(error) => {
+				Self::deposit_event(Event::<T>::LifecycleError(referendum_index, error));
+				T::WeightInfo::end_referendum()
+					.saturating_add(Self::abort_referendum(referendum_index))
+			},
+		}
+	}
+
+	/// Cancel a referendum that failed to move on to its next stage.
+	/// Returns the weight it took.
+	fn abort_referendum(referendum_index: ReferendumIndex) -> Weight {
+		match with_storage_layer(|| Self::do_cancel_referendum(referendum_index)) {
+			Ok(voters) => T::WeightInfo::cancel_referendum(voters),
+			Err(_) => {
+				frame_support::defensive!("failed referendum could not be cancelled");
+				// Keep the referendum from being processed again
+				ReferendumStatusOf::<T>::insert(
+					referendum_index,
+					ReferendumStatus::Cancelled { at: frame_system::Pallet::<T>::block_number() },
+				);
+				ActiveReferenda::<T>::mutate(|active| {
+					active.retain(|index| *index != referendum_index)
+				});
+				T::WeightInfo::cancel_referendum(0)
+			},
+		}
+	}
+
+	/// End a referendum whose voting period is over, enacting or scheduling the calls it
+	/// approved. Returns the weight it took, calls dispatched right away included.
+	fn end_referendum(referendum_index: ReferendumIndex) -> Result<Weight, DispatchError> {
+		ensure!(
+			matches!(
+				ReferendumStatusOf::<T>::get(referendum_index),
+				Some(ReferendumStatus::Voting { .. } | ReferendumStatus::Tallying)
+			),
+			Error::<T>::NoActiveReferendum
+		);
+		let end = frame_system::Pallet::<T>::block_number();
+
+		let mut finished_proposals = Vec::new();
+		let mut approved_calls = Vec::new();
+		let electorate = Self::electorate();
+
+		for proposal_index in 0..T::ProposalsPerReferendum::get() {
+			let ongoing_proposal_info =
+				match ReferendumInfo::<T>::get(referendum_index, proposal_index) {
+					Some(ProposalInfo::Ongoing(ongoing_proposal_info)) => ongoing_proposal_info,
+					_ => return Err(Error::<T>::ProposalNotOngoing.into()),
+				};
+
+			let rule = Self::approval_rule(ongoing_proposal_info.kind);
+			let approved = rule.approves(&ongoing_proposal_info.tally, electorate);
+			if approved && ongoing_proposal_info.kind.is_call() {
+				// The call is kept apart since its preimage is released right after
+				if let Some(preimage) =
+					ProposalPreimages::<T>::get(ongoing_proposal_info.proposal_hash)
+				{
+					approved_calls.push((
+						proposal_index,
+						ongoing_proposal_info.kind,
+						preimage.proposal,
+					));
+				}
+			}
+			Self::release_preimage(ongoing_proposal_info.proposal_hash);
+
+			// Voters get their points back the next time they vote
+			let (voters, points) =
+				ProposalParticipation::<T>::take(referendum_index, proposal_index);
+			let new_proposal_info = ProposalInfo::Finished(FinishedProposalInfo {
+				proposal_hash: ongoing_proposal_info.proposal_hash,
+				approved,
+				end,
+				quorum: rule.quorum_of(electorate),
+				tally: ongoing_proposal_info.tally,
+				voters,
+				points,
+			});
+
+			finished_proposals.push(new_proposal_info.clone());
+
+			ReferendumInfo::<T>::insert(referendum_index, proposal_index, new_proposal_info);
+
+			if let Some((proposer, deposit)) =
+				ProposalDeposits::<T>::take(referendum_index, proposal_index)
+			{
+				T::Currency::unreserve(&proposer, deposit);
+			}
+		}
+
+		// Update active referendum related variables
+		ReferendumStatusOf::<T>::insert(
+			referendum_index,
+			ReferendumStatus::Closed { ended_at: end },
+		);
+		ActiveReferenda::<T>::mutate(|active| active.retain(|index| *index != referendum_index));
+
+		Self::deposit_event(Event::<T>::ReferendumEnded(referendum_index, finished_proposals));
+
+		let mut weight = T::WeightInfo::end_referendum();
+		let enactment_period = T::EnactmentPeriod::get();
+		for (proposal_index, kind, encoded_call) in approved_calls {
+			let when = frame_system::Pallet::<T>::block_number().saturating_add(enactment_period);
+			Enactments::<T>::insert(when, (referendum_index, proposal_index), (kind, encoded_call));
+			weight.saturating_accrue(T::DbWeight::get().writes(1));
+			if !enactment_period.is_zero() {
+				Self::deposit_event(Event::<T>::EnactmentScheduled(
+					referendum_index,
+					proposal_index,
+					when,
+				));
+			}
+		}
+
+		Ok(weight)
+	}
+
+	/// Prune the oldest finished referendum once its retention period has passed, within
+	/// `remaining_weight`. Ballots are removed over as many blocks as needed, then its results
+	/// are replaced with a summary.
+	/// Returns the weight it took
+	fn prune_referendum(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+		let mut weight = T::DbWeight::get().reads(3);
+		if remaining_weight < weight.saturating_add(T::WeightInfo::prune_referendum(0)) {
+			return 0;
+		}
+
+		let referendum_index = NextToPrune::<T>::get();
+		if referendum_index >= ReferendumCount::<T>::get() {
+			return weight;
+		}
+		let ended_at = match ReferendumStatusOf::<T>::get(referendum_index) {
+			Some(ReferendumStatus::Closed { ended_at }) => ended_at,
+			Some(ReferendumStatus::Cancelled { at }) => at,
+			Some(_) => return weight,
+			// Nothing was stored for the referendum, there is nothing to prune
+			None => {
+				NextToPrune::<T>::put(referendum_index.saturating_add(1));
+				return weight.saturating_add(T::DbWeight::get().writes(1));
+			},
+		};
+		if now < ended_at.saturating_add(T::RetentionPeriod::get()) {
+			return weight;
+		}
+
+		// Ballots are removed first, as many as the remaining weight allows
+		let base_weight = T::WeightInfo::prune_referendum(0);
+		let ballot_weight = T::WeightInfo::prune_referendum(1).saturating_sub(base_weight).max(1);
+		let limit =
+			remaining_weight.saturating_sub(weight).saturating_sub(base_weight) / ballot_weight;
+		let limit = limit.min(u32::MAX as Weight) as u32;
+		let delegated = DelegatedBallots::<T>::clear_prefix(referendum_index, limit, None);
+		if delegated.maybe_cursor.is_some() || delegated.unique >= limit {
+			return weight.saturating_add(T::WeightInfo::prune_referendum(delegated.unique));
+		}
+		// Points not reclaimed yet are refunded along with their ballots
+		let ballot_limit = limit - delegated.unique;
+		let mut ballots: u32 = 0;
+		for (voter, ballot) in
+			VotingOf::<T>::drain_prefix(referendum_index).take(ballot_limit as usize)
+		{
+			Self::refund_ballot(referendum_index, &voter, ballot.points);
+			ballots.saturating_inc();
+		}
+		let removed = delegated.unique.saturating_add(ballots);
+		weight.saturating_accrue(T::WeightInfo::prune_referendum(removed));
+		if ballots >= ballot_limit {
+			return weight;
+		}
+
+		for proposal_index in 0..T::ProposalsPerReferendum::get() {
+			if let Some(ProposalInfo::Finished(finished_info)) =
+				ReferendumInfo::<T>::take(referendum_index, proposal_index)
+			{
+				ReferendumSummaries::<T>::insert(
+					referendum_index,
+					proposal_index,
+					ProposalSummary {
+						proposal_hash: finished_info.proposal_hash,
+						approved: finished_info.approved,
+						tally: finished_info.tally,
+					},
+				);
+			}
+		}
+		// Only the summaries of the latest pruned referenda are kept
+		if let Some(expired) = referendum_index.checked_sub(T::MaxSummarizedReferenda::get()) {
+			let _ = ReferendumSummaries::<T>::clear_prefix(
+				expired,
+				T::ProposalsPerReferendum::get(),
+				None,
+			);
+		}
+		ReferendumStatusOf::<T>::remove(referendum_index);
+		NextToPrune::<T>::put(referendum_index.saturating_add(1));
+
+		Self::deposit_event(Event::ReferendumPruned(referendum_index));
+
+		weight
+	}
+
+	/// Remove the proposals whose cooldown ends at `now` from the blacklist.
+	/// Each one was vetoed by a call in an earlier block, which bounds how many there are.
+	/// Returns the weight it took
+	fn expire_blacklist(now: T::BlockNumber) -> Weight {
+		let mut expired: Weight = 0;
+		for (proposal_hash, ()) in BlacklistExpiries::<T>::drain_prefix(now) {
+			// The proposal may have been vetoed again since
+			if Blacklist::<T>::get(proposal_hash) == Some(now) {
+				Blacklist::<T>::remove(proposal_hash);
+			}
+			expired.saturating_inc();
+		}
+		T::DbWeight::get().reads_writes(1 + 2 * expired, 2 * expired)
+	}
+
+	/// Votes every registered voter can cast on a proposal, without conviction
+	fn electorate() -> u32 {
+		VoterCount::<T>::get().saturating_mul(T::MaxVotes::get())
+	}
+
+	/// Turnout and majority a kind of proposal needs to be approved
+	fn approval_rule(kind: ProposalKind) -> ApprovalRule {
+		match kind {
+			ProposalKind::Text => T::TextApprovalRule::get(),
+			ProposalKind::Call => T::CallApprovalRule::get(),
+			ProposalKind::RootCall => T::RootCallApprovalRule::get(),
+		}
+	}
+
+	/// Cancel a referendum that hasn't reached a final stage, returning the deposits of its
+	/// proposals and the points of its ballots. Returns the number of voters whose points were
+	/// refunded.
+	fn do_cancel_referendum(referendum_index: ReferendumIndex) -> Result<u32, DispatchError> {
+		let status =
+			ReferendumStatusOf::<T>::get(referendum_index).ok_or(Error::<T>::NoActiveReferendum)?;
+		ensure!(!status.is_final(), Error::<T>::NoActiveReferendum);
+
+		let now = frame_system::Pallet::<T>::block_number();
+		ReferendumStatusOf::<T>::insert(referendum_index, ReferendumStatus::Cancelled { at: now });
+		for proposal_index in 0..T::ProposalsPerReferendum::get() {
+			match ReferendumInfo::<T>::get(referendum_index, proposal_index) {
+				Some(ProposalInfo::Ongoing(ongoing_proposal_info)) =>
+					Self::release_preimage(ongoing_proposal_info.proposal_hash),
+				Some(_) => {},
+				// Scheduled referenda have no proposals yet
+				None => continue,
+			}
+			ReferendumInfo::<T>::insert(
+				referendum_index,
+				proposal_index,
+				ProposalInfo::Cancelled(now),
+			);
+			ProposalParticipation::<T>::remove(referendum_index, proposal_index);
+
+			if let Some((proposer, deposit)) =
+				ProposalDeposits::<T>::take(referendum_index, proposal_index)
+			{
+				T::Currency::unreserve(&proposer, deposit);
+			}
+		}
+
+		let voters = Self::refund_points(referendum_index);
+
+		ActiveReferenda::<T>::mutate(|active| active.retain(|index| *index != referendum_index));
+
+		Self::deposit_event(Event::<T>::ReferendumCancelled(referendum_index));
+
+		Ok(voters)
+	}
+
+	/// Give back the points of every ballot submitted on a cancelled referendum.
+	/// Returns the number of voters
+	fn refund_points(referendum_index: ReferendumIndex) -> u32 {
+		let mut voters: u32 = 0;
+		for (voter, ballot) in VotingOf::<T>::iter_prefix(referendum_index) {
+			Self::refund_ballot(referendum_index, &voter, ballot.points);
+			voters.saturating_inc();
+		}
+		voters
+	}
+
+	/// Dispatch the approved calls due at `block_number` within the weight the block has left
+	/// after `used`, postponing the rest to the next block. The first call is always dispatched,
+	/// so that calls as heavy as a whole block, such as `set_code`, are enacted eventually.
+	/// Returns the weight it took
+	fn enact_due(block_number: T::BlockNumber, used: Weight) -> Weight {
+		let remaining_weight = T::BlockWeights::get()
+			.max_block
+			.saturating_sub(frame_system::Pallet::<T>::block_weight().total())
+			.saturating_sub(used);
+		let mut weight: Weight = 0;
+		let mut enacted_any = false;
+
+		for ((referendum_index, proposal_index), (kind, encoded_call)) in
+			Enactments::<T>::drain_prefix(block_number)
+		{
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			let call_weight =
+				Self::decode_call(&encoded_call).map_or(0, |call| call.get_dispatch_info().weight);
+			let needed = T::WeightInfo::enact_proposal().saturating_add(call_weight);
+
+			if enacted_any && needed > remaining_weight.saturating_sub(weight) {
+				let when = block_number.saturating_add(One::one());
+				Enactments::<T>::insert(
+					when,
+					(referendum_index, proposal_index),
+					(kind, encoded_call),
+				);
+				weight.saturating_accrue(T::DbWeight::get().writes(1));
+				Self::deposit_event(Event::<T>::EnactmentPostponed(
+					referendum_index,
+					proposal_index,
+					when,
+				));
+				continue;
+			}
+
+			weight.saturating_accrue(Self::enact(
+				referendum_index,
+				proposal_index,
+				kind,
+				&encoded_call,
+			));
+			enacted_any = true;
+		}
+
+		weight
+	}
+
+	/// Dispatch the call of an approved proposal with `Origin::Approved`, or with the root
+	/// origin for root calls, returning the weight it took
+	fn enact(
+		referendum_index: ReferendumIndex,
+		proposal_index: ProposalIndex,
+		kind: ProposalKind,
+		encoded_call: &[u8],
+	) -> Weight {
+		let mut weight = T::WeightInfo::enact_proposal();
+		let origin: T::RuntimeOrigin = match kind {
+			ProposalKind::RootCall => frame_system::RawOrigin::Root.into(),
+			ProposalKind::Call | ProposalKind::Text =>
+				Origin::Approved(referendum_index, proposal_index).into(),
+		};
+		let result = match Self::decode_call(encoded_call) {
+			Some(call) => {
+				weight.saturating_accrue(call.get_dispatch_info().weight);
+				call.dispatch(origin).map(|_| ()).map_err(|error| error.error)
+			},
+			None => Err(Error::<T>::InvalidCall.into()),
+		};
+
+		Self::deposit_event(Event::<T>::ProposalEnacted(referendum_index, proposal_index, result));
+
+		weight
+	}
+
+	fn decode_call(encoded_call: &[u8]) -> Option<<T as Config>::Call> {
+		<T as Config>::Call::decode(&mut &encoded_call[..]).ok()
+	}
+
+	fn do_submit_call_proposal(
+		who: T::AccountId,
+		call: &<T as Config>::Call,
+		kind: ProposalKind,
+	) -> DispatchResult {
+		ensure!(Self::is_a_voter(&who), Error::<T>::NotAVoter);
+
+		ensure!(
+			call.get_dispatch_info().weight <= T::MaxEnactmentWeight::get(),
+			Error::<T>::CallTooHeavy
+		);
+
+		let proposal: Proposal<T> =
+			call.encode().try_into().map_err(|()| Error::<T>::ProposalTooLong)?;
+		let proposal_hash = Self::hash_of(&proposal);
+
+		if !ProposalPreimages::<T>::contains_key(proposal_hash) {
+			// The proposal deposit already pays for the bytes of the preimage
+			Self::do_note_preimage(proposal, who.clone(), Zero::zero());
+		}
+
+		Self::do_submit_proposal(proposal_hash, who, kind)
+	}
+
+	fn do_submit_call_proposal_hash(
+		who: T::AccountId,
+		proposal_hash: T::Hash,
+		kind: ProposalKind,
+	) -> DispatchResult {
+		ensure!(Self::is_a_voter(&who), Error::<T>::NotAVoter);
+		let preimage =
+			ProposalPreimages::<T>::get(proposal_hash).ok_or(Error::<T>::PreimageMissing)?;
+		let call = Self::decode_call(&preimage.proposal).ok_or(Error::<T>::InvalidCall)?;
+		ensure!(
+			call.get_dispatch_info().weight <= T::MaxEnactmentWeight::get(),
+			Error::<T>::CallTooHeavy
+		);
+
+		Self::do_submit_proposal(proposal_hash, who, kind)
+	}
+
+	fn do_submit_proposal(
+		proposal_hash: T::Hash,
+		who: T::AccountId,
+		kind: ProposalKind,
+	) -> DispatchResult {
+		let now = frame_system::Pallet::<T>::block_number();
+		if let Some(until) = Blacklist::<T>::get(proposal_hash) {
+			ensure!(now >= until, Error::<T>::ProposalBlacklisted);
+			Blacklist::<T>::remove(proposal_hash);
+		}
+		let queued_proposals = Self::queued_proposals();
+		ensure!(
+			(queued_proposals.len() as u32) < T::ProposalQueueSize::get(),
+			Error::<T>::ProposalQueueFull
+		);
+		// Queued proposals are told apart by their hash
+		ensure!(
+			Self::queue_index_of(&queued_proposals, proposal_hash).is_err(),
+			Error::<T>::ProposalAlreadyQueued
+		);
+
+		let length = ProposalPreimages::<T>::get(proposal_hash)
+			.ok_or(Error::<T>::PreimageMissing)?
+			.proposal
+			.len() as u32;
+		let deposit = Self::proposal_deposit(length);
+		T::Currency::reserve(&who, deposit)?;
+
+		let queued_proposal = QueuedProposal {
+			proposal_hash,
+			proposer: who.clone(),
+			deposit,
+			kind,
+			seconds: Default::default(),
+			expires_at: frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::ProposalLifetime::get()),
+		};
+		QueuedProposals::<T>::try_append(queued_proposal)
+			.map_err(|()| Error::<T>::ProposalQueueFull)?;
+		ProposalPreimages::<T>::mutate(proposal_hash, |maybe_preimage| {
+			if let Some(preimage) = maybe_preimage {
+				preimage.references.saturating_inc();
+			}
+		});
+
+		Self::deposit_event(Event::ProposalSubmitted(proposal_hash, who));
+
+		Ok(())
+	}
+
+	/// Position in the queue of the proposal with `proposal_hash`
+	fn queue_index_of(
+		queued_proposals: &[QueuedProposalOf<T>],
+		proposal_hash: T::Hash,
+	) -> Result<usize, DispatchError> {
+		queued_proposals
+			.iter()
+			.position(|queued_proposal| queued_proposal.proposal_hash == proposal_hash)
+			.ok_or_else(|| Error::<T>::ProposalNotInQueue.into())
+	}
+
+	/// Remove the proposal with `proposal_hash` from the queue
+	fn take_queued_proposal(proposal_hash: T::Hash) -> Result<QueuedProposalOf<T>, DispatchError> {
+		let mut queued_proposals = Self::queued_proposals();
+		let queue_index = Self::queue_index_of(&queued_proposals, proposal_hash)?;
+
+		let queued_proposal = queued_proposals.remove(queue_index);
+		QueuedProposals::<T>::put(queued_proposals);
+
+		Ok(queued_proposal)
+	}
+
+	/// Release a proposal that left the queue without being picked, returning every deposit
+	/// held for it
+	fn drop_queued_proposal(queued_proposal: QueuedProposalOf<T>) {
+		T::Currency::unreserve(&queued_proposal.proposer, queued_proposal.deposit);
+		Self::refund_seconds(queued_proposal.proposal_hash);
+		Self::release_preimage(queued_proposal.proposal_hash);
+	}
+
+	/// Drop up to `T::MaxExpiredProposals` proposals that waited too long in the queue.
+	/// Returns the weight it took.
+	fn expire_proposals(now: T::BlockNumber) -> Weight {
+		let mut queued_proposals = Self::queued_proposals();
+		let max_expired = T::MaxExpiredProposals::get() as usize;
+		let mut expired_proposals = Vec::new();
+		queued_proposals.retain(|queued_proposal| {
+			let expired =
+				expired_proposals.len() < max_expired && now >= queued_proposal.expires_at;
+			if expired {
+				expired_proposals.push(queued_proposal.clone());
+			}
+			!expired
+		});
+
+		let expired = expired_proposals.len() as u32;
+		if expired > 0 {
+			QueuedProposals::<T>::put(queued_proposals);
+		}
+		for queued_proposal in expired_proposals {
+			let proposal_hash = queued_proposal.proposal_hash;
+			Self::drop_queued_proposal(queued_proposal);
+			Self::deposit_event(Event::ProposalExpired(proposal_hash));
+		}
+
+		T::WeightInfo::expire_proposals(expired)
+	}
+
+	/// Return the deposits of the voters that seconded a proposal
+	fn refund_seconds(proposal_hash: T::Hash) {
+		for (second, deposit) in SecondsOf::<T>::drain_prefix(proposal_hash) {
+			T::Currency::unreserve(&second, deposit);
+		}
+	}
+
+	fn do_note_preimage(proposal: Proposal<T>, provider: T::AccountId, deposit: BalanceOf<T>) {
+		let proposal_hash = Self::hash_of(&proposal);
+		let preimage =
+			ProposalPreimage { proposal, provider: provider.clone(), deposit, references: 0 };
+		ProposalPreimages::<T>::insert(proposal_hash, preimage);
+		Self::deposit_event(Event::PreimageNoted(proposal_hash, provider));
+	}
+
+	/// Drop one reference to a preimage, removing it once nothing refers to it anymore
+	fn release_preimage(proposal_hash: T::Hash) {
+		if let Some(mut preimage) = ProposalPreimages::<T>::get(proposal_hash) {
+			preimage.references.saturating_dec();
+			if preimage.references == 0 {
+				Self::do_clear_preimage(proposal_hash, preimage);
+			} else {
+				ProposalPreimages::<T>::insert(proposal_hash, preimage);
+			}
+		}
+	}
+
+	fn do_clear_preimage(proposal_hash: T::Hash, preimage: ProposalPreimageOf<T>) {
+		T::Currency::unreserve(&preimage.provider, preimage.deposit);
+		ProposalPreimages::<T>::remove(proposal_hash);
+		Self::deposit_event(Event::PreimageCleared(proposal_hash));
+	}
+
+	fn hash_of(proposal: &Proposal<T>) -> T::Hash {
+		<<T as frame_system::Config>::Hashing as Hasher>::hash(proposal)
+	}
+
+	/// Deposit to be reserved for a proposal of `length` bytes
+	pub fn proposal_deposit(length: u32) -> BalanceOf<T> {
+		T::ProposalDepositPerByte::get()
+			.saturating_mul(length.into())
+			.saturating_add(T::ProposalDepositBase::get())
+	}
+
+	/// Active referenda, oldest first, with their proposals and live tallies
+	pub fn referenda_details() -> Vec<ReferendumDetails<T::Hash, T::BlockNumber>> {
+		Self::active_referenda()
+			.into_iter()
+			.filter_map(Self::referendum_details)
+			.collect()
+	}
+
+	/// Proposals and live tallies of a referendum, `None` if it is not active
+	pub fn referendum_details(
+		index: ReferendumIndex,
+	) -> Option<ReferendumDetails<T::Hash, T::BlockNumber>> {
+		let ends_at = Self::voting_ends_at(index)?;
+		let proposals = (0..T::ProposalsPerReferendum::get())
+			.filter_map(|proposal_index| match ReferendumInfo::<T>::get(index, proposal_index)? {
+				ProposalInfo::Ongoing(info) => Some(ProposalDetails {
+					index: proposal_index,
+					proposal_hash: info.proposal_hash,
+					tally: info.tally,
+				}),
+				ProposalInfo::Finished(_) | ProposalInfo::Cancelled(_) => None,
+			})
+			.collect();
+		Some(ReferendumDetails { index, ends_at, proposals })
+	}
+
+	/// Proposals waiting in the queue, in the order they were submitted
+	pub fn proposal_queue() -> Vec<
+		QueuedProposal<
+			T::AccountId,
+			BalanceOf<T>,
+			T::Hash,
+			T::BlockNumber,
+			Vec<(T::AccountId, BalanceOf<T>)>,
+		>,
+	> {
+		Self::queued_proposals()
+			.into_iter()
+			.map(|queued_proposal| QueuedProposal {
+				proposal_hash: queued_proposal.proposal_hash,
+				proposer: queued_proposal.proposer,
+				deposit: queued_proposal.deposit,
+				kind: queued_proposal.kind,
+				seconds: SecondsOf::<T>::iter_prefix(queued_proposal.proposal_hash).collect(),
+				expires_at: queued_proposal.expires_at,
+			})
+			.collect()
+	}
+
+	/// Text of a proposal, if its preimage is stored
+	pub fn proposal_text(proposal_hash: T::Hash) -> Option<Vec<u8>> {
+		ProposalPreimages::<T>::get(proposal_hash).map(|preimage| preimage.proposal.into_inner())
+	}
+
+	/// Points that submitting `votes` would take from a voter, saturating on overflow
+	pub fn ballot_cost(votes: &[(u32, Vote)]) -> Points {
+		T::VoteCost::ballot_cost(votes.iter().map(|(amount, _)| *amount)).unwrap_or(Points::MAX)
+	}
+
+	/// Remaining points of `account` and the active referenda it voted in,
+	/// `None` if it is not a voter
+	pub fn voter_status(account: &T::AccountId) -> Option<VoterStatus> {
+		let points =
+			VoterPoints::<T>::get(account)?.saturating_add(Self::reclaimable_points(account));
+		let voted_in = Self::active_referenda()
+			.into_iter()
+			.filter(|index| Self::already_voted(*index, account))
+			.collect();
+		Some(VoterStatus { points, voted_in })
+	}
+
+	/// Ballots `account` submitted on every referendum, oldest first
+	pub fn ballots_of(
+		account: &T::AccountId,
+	) -> Vec<(ReferendumIndex, Ballot<Vec<(ProposalIndex, u32, Vote)>, BalanceOf<T>>)> {
+		(0..Self::referendum_count())
+			.filter_map(|index| {
+				let Ballot { votes, points, conviction, balance } =
+					VotingOf::<T>::get(index, account)?;
+				Some((index, Ballot { votes: votes.into_inner(), points, conviction, balance }))
+			})
+			.collect()
+	}
+
+	/// Block at which the next referendum will be launched
+	pub fn next_launch() -> T::BlockNumber {
+		let now = frame_system::Pallet::<T>::block_number();
+		let launch_period = T::LaunchPeriod::get();
+		now.saturating_sub(now % launch_period).saturating_add(launch_period)
+	}
+
+	/// Balance that has to be locked to cast `votes` with conviction
+	fn min_conviction_balance(votes: &[(ProposalIndex, u32, Vote)]) -> BalanceOf<T> {
+		let total = votes.iter().fold(0u32, |total, (_, amount, _)| total.saturating_add(*amount));
+		T::MinConvictionBalance::get().saturating_mul(total.into())
+	}
+
+	/// Points a ballot costs, checking it only votes once on proposals of the referendum and
+	/// never more than `T::MaxVotes` times
+	fn checked_ballot_cost(votes: &[(ProposalIndex, u32, Vote)]) -> Result<Points, DispatchError> {
+		ensure!(!votes.is_empty(), Error::<T>::EmptyBallot);
+		for (position, (proposal_index, amount, _)) in votes.iter().enumerate() {
+			ensure!(
+				*proposal_index < T::ProposalsPerReferendum::get(),
+				Error::<T>::ProposalNotInReferendum
+			);
+			ensure!(*amount <= T::MaxVotes::get(), Error::<T>::TooManyVotes);
+			ensure!(
+				!votes[..position].iter().any(|(other, _, _)| other == proposal_index),
+				Error::<T>::DuplicateVote
+			);
+		}
+		T::VoteCost::ballot_cost(votes.iter().map(|(_, amount, _)| *amount))
+			.ok_or_else(|| Error::<T>::Overflow.into())
+	}
+
+	/// Apply each of `votes`, multiplied by `conviction`, to the tally of its proposal using
+	/// `update`, either adding or subtracting them. The turnout counts them without conviction.
+	fn update_tallies(
+		referendum_index: ReferendumIndex,
+		votes: &[(ProposalIndex, u32, Vote)],
+		conviction: Conviction,
+		update: fn(u32, u32) -> Option<u32>,
+	) -> DispatchResult {
+		for (proposal_index, amount, vote) in votes.iter() {
+			let cost = T::VoteCost::cost(*amount).ok_or(Error::<T>::Overflow)?;
+			ProposalParticipation::<T>::try_mutate(
+				referendum_index,
+				proposal_index,
+				|(voters, points)| -> DispatchResult {
+					*voters = update(*voters, 1).ok_or(Error::<T>::Overflow)?;
+					*points = update(*points, cost).ok_or(Error::<T>::Overflow)?;
+					Ok(())
+				},
+			)?;
+
+			let weighted =
+				amount.checked_mul(conviction.votes_multiplier()).ok_or(Error::<T>::Overflow)?;
+			ReferendumInfo::<T>::try_mutate(
+				referendum_index,
+				proposal_index,
+				|maybe_info| -> DispatchResult {
+					let ongoing_info = match maybe_info {
+						Some(ProposalInfo::Ongoing(ongoing_info)) => ongoing_info,
+						_ => return Err(Error::<T>::NoActiveReferendum.into()),
+					};
+					let tally = match vote {
+						Vote::Aye => &mut ongoing_info.tally.aye_votes,
+						Vote::Nay => &mut ongoing_info.tally.nay_votes,
+						Vote::Abstain => &mut ongoing_info.tally.abstain_votes,
+					};
+					*tally = update(*tally, weighted).ok_or(Error::<T>::Overflow)?;
+					let turnout = &mut ongoing_info.tally.turnout;
+					*turnout = update(*turnout, *amount).ok_or(Error::<T>::Overflow)?;
+					Ok(())
+				},
+			)?;
+		}
+		Ok(())
+	}
+
+	fn is_a_voter(account: &T::AccountId) -> bool {
+		VoterPoints::<T>::get(account).is_some()
+	}
+
+	fn is_active(referendum_index: ReferendumIndex) -> bool {
+		Self::voting_ends_at(referendum_index).is_some()
+	}
+
+	/// Block at which voting on the referendum ends, `None` if it isn't accepting votes
+	fn voting_ends_at(referendum_index: ReferendumIndex) -> Option<T::BlockNumber> {
+		match ReferendumStatusOf::<T>::get(referendum_index)? {
+			ReferendumStatus::Voting { ends_at } => Some(ends_at),
+			_ => None,
+		}
 	}
 
-	fn already_voted(account: &T::AccountId) -> bool {
-		let available_points =
-			VoterPoints::<T>::get(account).expect("check should be done outside this function");
-		available_points < T::MaxVotes::get().pow(2)
+	fn already_voted(referendum_index: ReferendumIndex, account: &T::AccountId) -> bool {
+		VotingOf::<T>::contains_key(referendum_index, account)
 	}
 }
@@ -0,0 +1,244 @@
+//! Migrations of the storage of pallet-quadratic-voting, each one taking the pallet's storage
+//! version one step further
+//!
+//! Version 0 is the layout of the first release, before storage versions were introduced.
+//! Every storage item added since then starts empty, so only the items that changed layout are
+//! migrated.
+
+use crate::*;
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use sp_std::marker::PhantomData;
+
+/// Target of the logs emitted while migrating
+const LOG_TARGET: &str = "runtime::quadratic-voting";
+
+/// Storage layout before storage versions were introduced
+mod v0 {
+	use super::*;
+
+	/// Points every voter was registered with, spent points were never refunded
+	pub const POINTS: Points = 100;
+
+	/// A proposal waiting in the queue, only its text was kept
+	pub type Proposal = Vec<u8>;
+
+	#[derive(Encode, Decode)]
+	pub struct Tally {
+		pub aye_votes: u32,
+		pub nay_votes: u32,
+	}
+
+	#[derive(Encode, Decode)]
+	pub struct OngoingProposalInfo<Hash> {
+		pub proposal_hash: Hash,
+		pub tally: Tally,
+	}
+
+	#[derive(Encode, Decode)]
+	pub struct FinishedProposalInfo<BlockNumber> {
+		pub approved: bool,
+		pub end: BlockNumber,
+	}
+
+	#[derive(Encode, Decode)]
+	pub enum ProposalInfo<Hash, BlockNumber> {
+		Ongoing(OngoingProposalInfo<Hash>),
+		Finished(FinishedProposalInfo<BlockNumber>),
+	}
+}
+
+pub mod v1 {
+	use super::*;
+	use frame_support::{storage::migration, traits::PalletInfoAccess};
+
+	/// Moves `ReferendumInfo` to the format of `FinishedProposalInfo` that keeps the proposal,
+	/// its final tally and turnout, records the `ReferendumStatusOf` of every referendum and
+	/// counts the registered voters into `VoterCount`.
+	///
+	/// Results finished under the old format only kept whether they were approved, so the rest
+	/// of their fields are left empty. Proposals still ongoing can't be tracked by the referenda
+	/// of the new format, so they are marked as cancelled, their referendum is counted in
+	/// `ReferendumCount` and the old `ActiveReferendum` and `ReferendumEndsAt` values are removed.
+	///
+	/// The old format never refunded points and kept no record of ballots, so every voter is
+	/// refunded the points they spent, and topped up to `T::InitialPoints` if that is more.
+	/// Queued proposals kept neither a proposer nor a deposit and can't join the new queue, so
+	/// it is cleared, logging the hash and text of each dropped proposal so it can be submitted
+	/// again.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut translated: u64 = 0;
+			let mut ongoing_referendum = None;
+			ReferendumInfo::<T>::translate::<v0::ProposalInfo<T::Hash, T::BlockNumber>, _>(
+				|referendum_index, _, old_proposal_info| {
+					translated.saturating_inc();
+					Some(match old_proposal_info {
+						v0::ProposalInfo::Ongoing(_) => {
+							ongoing_referendum = Some(referendum_index);
+							ReferendumStatusOf::<T>::insert(
+								referendum_index,
+								ReferendumStatus::Cancelled { at: now },
+							);
+							ProposalInfo::Cancelled(now)
+						},
+						v0::ProposalInfo::Finished(finished_info) => {
+							let ended_at = finished_info.end;
+							ReferendumStatusOf::<T>::insert(
+								referendum_index,
+								ReferendumStatus::Closed { ended_at },
+							);
+							ProposalInfo::Finished(FinishedProposalInfo {
+								proposal_hash: Default::default(),
+								approved: finished_info.approved,
+								end: finished_info.end,
+								quorum: 0,
+								tally: Default::default(),
+								voters: 0,
+								points: 0,
+							})
+						},
+					})
+				},
+			);
+
+			// The old format only counted a referendum once it ended
+			if let Some(referendum_index) = ongoing_referendum {
+				ReferendumCount::<T>::mutate(|count| {
+					*count = (*count).max(referendum_index.saturating_add(1))
+				});
+			}
+			let pallet_name = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+			migration::take_storage_value::<()>(pallet_name, b"ActiveReferendum", &[]);
+			migration::take_storage_value::<T::BlockNumber>(pallet_name, b"ReferendumEndsAt", &[]);
+			let old_queue = migration::take_storage_value::<Vec<v0::Proposal>>(
+				pallet_name,
+				b"QueuedProposals",
+				&[],
+			)
+			.unwrap_or_default();
+			for proposal in old_queue.iter() {
+				log::warn!(
+					target: LOG_TARGET,
+					"dropping queued proposal {:?}: {:?}",
+					<<T as frame_system::Config>::Hashing as Hasher>::hash(proposal),
+					sp_std::str::from_utf8(proposal).unwrap_or("<not UTF-8>"),
+				);
+			}
+
+			let mut voters: u32 = 0;
+			VoterPoints::<T>::translate::<Points, _>(|who, points| {
+				voters.saturating_inc();
+				let refund = v0::POINTS.saturating_sub(points);
+				if refund > 0 {
+					log::info!(target: LOG_TARGET, "refunding {} points to {:?}", refund, who);
+				}
+				Some(Self::points_back())
+			});
+			VoterCount::<T>::put(voters);
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(
+				translated.saturating_add(voters as u64).saturating_add(4),
+				translated.saturating_mul(2).saturating_add(voters as u64).saturating_add(6),
+			)
+		}
+
+		// `Executive::try_runtime_upgrade` runs the checks of every migration before and after all
+		// of them, so they are skipped on chains this migration doesn't apply to
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			let migrating = Pallet::<T>::on_chain_storage_version() == 0;
+			Self::set_temp_storage(migrating, "migrating");
+			if !migrating {
+				return Ok(());
+			}
+
+			let pallet_name = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+			let dropped = migration::get_storage_value::<Vec<v0::Proposal>>(
+				pallet_name,
+				b"QueuedProposals",
+				&[],
+			)
+			.map_or(0, |old_queue| old_queue.len() as u32);
+			Self::set_temp_storage(dropped, "dropped");
+			let proposals = ReferendumInfo::<T>::iter_keys().count() as u32;
+			Self::set_temp_storage(proposals, "proposals");
+			let voters = VoterPoints::<T>::iter_keys().count() as u32;
+			Self::set_temp_storage(voters, "voters");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			let migrating: bool =
+				Self::get_temp_storage("migrating").ok_or("migration flag was not stored")?;
+			if !migrating {
+				return Ok(());
+			}
+
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"storage version has to be 1 after migrating"
+			);
+			let proposals: u32 =
+				Self::get_temp_storage("proposals").ok_or("proposal count was not stored")?;
+			frame_support::ensure!(
+				ReferendumInfo::<T>::iter_values().count() as u32 == proposals,
+				"every proposal has to decode with the new format"
+			);
+			let voters: u32 =
+				Self::get_temp_storage("voters").ok_or("voter count was not stored")?;
+			frame_support::ensure!(
+				VoterCount::<T>::get() == voters,
+				"voter count has to match the registered voters"
+			);
+			frame_support::ensure!(
+				VoterPoints::<T>::iter_values().all(|points| points == Self::points_back()),
+				"every voter has to get their points back"
+			);
+			frame_support::ensure!(
+				ReferendumInfo::<T>::iter_keys()
+					.all(|(referendum_index, _)| referendum_index < ReferendumCount::<T>::get()),
+				"every referendum has to be counted"
+			);
+			frame_support::ensure!(
+				ReferendumInfo::<T>::iter_keys().all(|(referendum_index, _)| {
+					ReferendumStatusOf::<T>::get(referendum_index)
+						.map_or(false, |status| status.is_final())
+				}),
+				"every referendum has to be closed or cancelled"
+			);
+			let pallet_name = <Pallet<T> as PalletInfoAccess>::name().as_bytes();
+			frame_support::ensure!(
+				!migration::have_storage_value(pallet_name, b"ActiveReferendum", &[]) &&
+					!migration::have_storage_value(pallet_name, b"ReferendumEndsAt", &[]),
+				"the old referendum values have to be removed"
+			);
+			let dropped: u32 =
+				Self::get_temp_storage("dropped").ok_or("dropped proposal count was not stored")?;
+			frame_support::ensure!(
+				!migration::have_storage_value(pallet_name, b"QueuedProposals", &[]),
+				"the old proposal queue has to be cleared"
+			);
+			log::info!(target: LOG_TARGET, "dropped {} queued proposals", dropped);
+			Ok(())
+		}
+	}
+
+	impl<T: Config> MigrateToV1<T> {
+		/// Points of every voter once the points they spent are refunded
+		fn points_back() -> Points {
+			v0::POINTS.max(T::InitialPoints::get())
+		}
+	}
+}
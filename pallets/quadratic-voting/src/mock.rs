@@ -1,13 +1,16 @@
 use crate as pallet_quadratic_voting;
+use crate::{ApprovalRule, VoteThreshold};
 use frame_support::{
 	parameter_types,
-	traits::{ConstU16, ConstU32, ConstU64, GenesisBuild, Hooks},
+	traits::{ConstU16, ConstU32, ConstU64, EitherOfDiverse, GenesisBuild, Hooks},
 };
 use frame_system as system;
+use frame_system::EnsureRoot;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Perbill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -71,27 +74,77 @@ impl pallet_balances::Config for Test {
 
 parameter_types! {
 	pub const LaunchPeriod: BlockNumber = 2;
-	pub const VotingPeriod: BlockNumber = 1;
+	pub static VotingPeriod: BlockNumber = 1;
+	pub static ProposalQueueSize: u32 = 2;
+	pub static MaxExpiredProposals: u32 = 2;
+	pub static EnactmentPeriod: BlockNumber = 0;
+	pub static MaxEnactmentWeight: u64 = u64::MAX;
+	pub static MaxSummarizedReferenda: u32 = 2;
+	pub static TextApprovalRule: ApprovalRule =
+		ApprovalRule { threshold: VoteThreshold::SimpleMajority, quorum: Perbill::zero() };
+	pub static CallApprovalRule: ApprovalRule =
+		ApprovalRule { threshold: VoteThreshold::SimpleMajority, quorum: Perbill::zero() };
+	pub static RootCallApprovalRule: ApprovalRule =
+		ApprovalRule { threshold: VoteThreshold::SimpleMajority, quorum: Perbill::zero() };
 }
 
+/// Root or a call approved in a referendum
+type GovernanceOrigin =
+	EitherOfDiverse<EnsureRoot<u64>, pallet_quadratic_voting::EnsureQuadraticMajority>;
+
 impl pallet_quadratic_voting::Config for Test {
 	type Event = Event;
 	type MaxProposalLength = ConstU32<50>;
 	type LaunchPeriod = LaunchPeriod;
 	type VotingPeriod = VotingPeriod;
-	type ProposalQueueSize = ConstU32<2>;
+	type MaxActiveReferenda = ConstU32<2>;
+	type ProposalQueueSize = ProposalQueueSize;
+	type ProposalLifetime = ConstU64<10>;
+	type MaxExpiredProposals = MaxExpiredProposals;
+	type MaxSeconds = ConstU32<2>;
+	type SecondDeposit = ConstU64<5>;
 	type MaxVotes = ConstU32<10>;
+	type InitialPoints = ConstU32<100>;
+	type VoteCost = pallet_quadratic_voting::QuadraticCost;
+	type MaxDelegators = ConstU32<3>;
 	type Identity = Identity;
 	type ProposalsPerReferendum = ConstU32<2>;
+	type Currency = Balances;
+	type ProposalDepositBase = ConstU64<10>;
+	type ProposalDepositPerByte = ConstU64<1>;
+	type PreimageByteDeposit = ConstU64<2>;
+	type ConvictionPeriod = ConstU64<4>;
+	type MinConvictionBalance = ConstU64<10>;
+	type RuntimeOrigin = Origin;
+	type Call = Call;
+	type EnactmentPeriod = EnactmentPeriod;
+	type MaxEnactmentWeight = MaxEnactmentWeight;
+	type ModeratorOrigin = GovernanceOrigin;
+	type TextApprovalRule = TextApprovalRule;
+	type CallApprovalRule = CallApprovalRule;
+	type RootCallApprovalRule = RootCallApprovalRule;
+	type RetentionPeriod = ConstU64<5>;
+	type MaxSummarizedReferenda = MaxSummarizedReferenda;
+	type RegistrationOrigin = GovernanceOrigin;
+	type CancellationOrigin = GovernanceOrigin;
+	type VetoOrigin = GovernanceOrigin;
+	type CooldownPeriod = ConstU64<20>;
+	type WeightInfo = ();
 }
 
 impl pallet_basic_identity::Config for Test {
 	type Event = Event;
+	type IdentityOrigin = GovernanceOrigin;
+	type OnIdentityChanged = QuadraticVoting;
+	type WeightInfo = ();
 }
 
 /// Builds genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 1_000), (2, 1_000)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
 	pallet_quadratic_voting::GenesisConfig::<Test> { voters: vec![1], ..Default::default() }
 		.assimilate_storage(&mut t)
 		.unwrap();
@@ -0,0 +1,47 @@
+//! Runtime API for querying the state of quadratic voting without decoding raw storage
+
+use crate::{
+	Ballot, ProposalIndex, QueuedProposal, ReferendumDetails, ReferendumIndex, Vote, VoterStatus,
+};
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Referenda, voters and proposal queue of the quadratic voting pallet
+	pub trait QuadraticVotingApi<AccountId, Balance, Hash, BlockNumber>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		Hash: Codec,
+		BlockNumber: Codec,
+	{
+		/// Referenda that are accepting votes, with their proposals and live tallies
+		fn active_referenda() -> Vec<ReferendumDetails<Hash, BlockNumber>>;
+
+		/// Remaining points of `who` and the active referenda it already voted in,
+		/// `None` if `who` is not a voter
+		fn voter_status(who: AccountId) -> Option<VoterStatus>;
+
+		/// Proposals waiting in the queue, in the order they were submitted
+		fn queued_proposals() -> Vec<
+			QueuedProposal<AccountId, Balance, Hash, BlockNumber, Vec<(AccountId, Balance)>>,
+		>;
+
+		/// Block at which the next referendum will be launched
+		fn next_launch() -> BlockNumber;
+
+		/// Proposals and live tallies of a referendum, `None` if it is not active
+		fn referendum(index: ReferendumIndex) -> Option<ReferendumDetails<Hash, BlockNumber>>;
+
+		/// Text of a proposal, if its preimage is stored
+		fn proposal_text(proposal_hash: Hash) -> Option<Vec<u8>>;
+
+		/// Points that submitting `votes` would take from a voter
+		fn ballot_cost(votes: Vec<(u32, Vote)>) -> u32;
+
+		/// Ballots `who` submitted on every referendum, oldest first
+		fn ballots(
+			who: AccountId,
+		) -> Vec<(ReferendumIndex, Ballot<Vec<(ProposalIndex, u32, Vote)>, Balance>)>;
+	}
+}
@@ -1,6 +1,22 @@
 use crate as pallet_quadratic_voting;
-use crate::{mock::*, types::Vote, Error};
-use frame_support::{assert_noop, assert_ok, pallet_prelude::*};
+use crate::{
+	migrations::v1::MigrateToV1, mock::*, types::Vote, ApprovalRule, Ballot, Conviction,
+	EnsureQuadraticMajority, Error, FinishedProposalInfo, ProposalInfo, ProposalKind,
+	ProposalSummary, ReferendumStatus, Tally, VoteThreshold, WeightInfo,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::*,
+	storage::{storage_prefix, unhashed},
+	traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::GetDispatchInfo,
+};
+use proptest::prelude::{prop_oneof, proptest, Just, Strategy};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BadOrigin, BlakeTwo256, Hash},
+	Perbill,
+};
 
 #[test]
 fn submitting_a_proposal_adds_it_to_queued_proposals() {
@@ -10,7 +26,12 @@ fn submitting_a_proposal_adds_it_to_queued_proposals() {
 			"Should we buy DOT?".encode()
 		));
 		assert_eq!(QuadraticVoting::queued_proposals().len(), 1);
-		assert_eq!(QuadraticVoting::queued_proposals()[0], "Should we buy DOT?".encode());
+		let proposal_hash = BlakeTwo256::hash(&"Should we buy DOT?".encode());
+		assert_eq!(QuadraticVoting::queued_proposals()[0].proposal_hash, proposal_hash);
+		assert_eq!(
+			QuadraticVoting::proposal_preimages(proposal_hash).unwrap().proposal,
+			"Should we buy DOT?".encode()
+		);
 	});
 }
 
@@ -45,37 +66,44 @@ fn error_if_queue_full() {
 #[test]
 fn referendum_is_started_after_launch_period_blocks() {
 	new_test_ext().execute_with(|| {
-		assert_eq!(QuadraticVoting::active_referendum(), None);
+		assert!(QuadraticVoting::active_referenda().is_empty());
 		QuadraticVoting::submit_proposal(Origin::signed(1), "Should we fill the queue?".encode())
 			.unwrap();
 		QuadraticVoting::submit_proposal(Origin::signed(1), "Should we do it?".encode()).unwrap();
 		run_to_block(<Test as pallet_quadratic_voting::Config>::LaunchPeriod::get());
-		assert_eq!(QuadraticVoting::active_referendum(), Some(()));
+		assert_eq!(QuadraticVoting::active_referenda().into_inner(), vec![0]);
 	});
 }
 
 #[test]
 fn referendum_not_started_no_proposals_in_queue() {
 	new_test_ext().execute_with(|| {
-		assert_eq!(QuadraticVoting::active_referendum(), None);
+		assert!(QuadraticVoting::active_referenda().is_empty());
 		run_to_block(<Test as pallet_quadratic_voting::Config>::LaunchPeriod::get());
-		assert_eq!(QuadraticVoting::active_referendum(), None);
+		assert!(QuadraticVoting::active_referenda().is_empty());
 	});
 }
 
 #[test]
 fn referendum_closes_after_voting_period_blocks() {
 	new_test_ext().execute_with(|| {
-		assert_eq!(QuadraticVoting::active_referendum(), None);
+		assert!(QuadraticVoting::active_referenda().is_empty());
 		QuadraticVoting::submit_proposal(Origin::signed(1), "Should we fill the queue?".encode())
 			.unwrap();
 		QuadraticVoting::submit_proposal(Origin::signed(1), "Should we do it?".encode()).unwrap();
 		assert_eq!(QuadraticVoting::queued_proposals().len(), 2);
 		run_to_block(LaunchPeriod::get());
-		assert_eq!(QuadraticVoting::active_referendum(), Some(()));
-		assert_eq!(QuadraticVoting::referendum_ends_at(), 3);
+		assert_eq!(QuadraticVoting::active_referenda().into_inner(), vec![0]);
+		assert_eq!(
+			QuadraticVoting::referendum_status(0),
+			Some(ReferendumStatus::Voting { ends_at: 3 })
+		);
 		next_block();
-		assert_eq!(QuadraticVoting::active_referendum(), None);
+		assert!(QuadraticVoting::active_referenda().is_empty());
+		assert_eq!(
+			QuadraticVoting::referendum_status(0),
+			Some(ReferendumStatus::Closed { ended_at: 3 })
+		);
 		assert_eq!(QuadraticVoting::queued_proposals().len(), 0);
 	});
 }
@@ -92,10 +120,11 @@ fn user_submits_vote_happy_path() {
 			"Should we buy KSM?".encode()
 		));
 		run_to_block(LaunchPeriod::get());
-		assert_eq!(QuadraticVoting::active_referendum(), Some(()));
+		assert_eq!(QuadraticVoting::active_referenda().into_inner(), vec![0]);
 		assert_ok!(QuadraticVoting::submit_votes(
 			Origin::signed(1),
-			BoundedVec::truncate_from(vec![(5, Vote::Aye), (2, Vote::Nay)])
+			0,
+			BoundedVec::truncate_from(vec![(0, 5, Vote::Aye), (1, 2, Vote::Nay)])
 		));
 		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_aye_votes(), 5);
 		assert_eq!(QuadraticVoting::voter_points(1u64).unwrap(), 71u32);
@@ -118,7 +147,8 @@ fn non_voter_should_not_be_allowed_to_submit_anything() {
 		assert_noop!(
 			QuadraticVoting::submit_votes(
 				Origin::signed(2),
-				BoundedVec::truncate_from(vec![(1, Vote::Nay)])
+				0,
+				BoundedVec::truncate_from(vec![(0, 1, Vote::Nay)])
 			),
 			Error::<Test>::NotAVoter
 		);
@@ -134,7 +164,8 @@ fn try_vote_with_not_enough_points() {
 		assert_noop!(
 			QuadraticVoting::submit_votes(
 				Origin::signed(1),
-				BoundedVec::truncate_from(vec![(11, Vote::Nay), (2, Vote::Aye)])
+				0,
+				BoundedVec::truncate_from(vec![(0, 10, Vote::Nay), (1, 2, Vote::Aye)])
 			),
 			Error::<Test>::NotEnoughPoints
 		);
@@ -143,27 +174,1987 @@ fn try_vote_with_not_enough_points() {
 
 #[test]
 fn double_voting_fails() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 5, Vote::Nay)])
+		));
+		assert_noop!(
+			QuadraticVoting::submit_votes(
+				Origin::signed(1),
+				0,
+				BoundedVec::truncate_from(vec![(1, 1, Vote::Nay)])
+			),
+			Error::<Test>::AlreadyVoted
+		);
+	});
+}
+
+#[test]
+fn ballots_can_leave_proposals_out() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(1, 6, Vote::Aye)])
+		));
+		assert_eq!(QuadraticVoting::voter_points(1), Some(64));
+		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_aye_votes(), 0);
+		assert_eq!(QuadraticVoting::referendum_info(0, 1).unwrap().get_aye_votes(), 6);
+	});
+}
+
+#[test]
+fn abstaining_counts_toward_turnout_only() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Aye), (1, 3, Vote::Abstain)])
+		));
+		assert_eq!(QuadraticVoting::voter_points(1), Some(90));
+		let details = QuadraticVoting::referendum_details(0).unwrap();
+		assert_eq!(details.proposals[0].tally.turnout, 1);
+		assert_eq!(details.proposals[1].tally.abstain_votes, 3);
+		assert_eq!(details.proposals[1].tally.turnout, 3);
+
+		// Abstentions can't make a proposal pass
+		next_block();
+		assert!(finished_info(0, 0).approved);
+		assert!(!finished_info(0, 1).approved);
+	});
+}
+
+fn finished_info(referendum_index: u32, proposal_index: u32) -> FinishedProposalInfo<H256, u64> {
+	match QuadraticVoting::referendum_info(referendum_index, proposal_index) {
+		Some(ProposalInfo::Finished(finished_info)) => finished_info,
+		other => panic!("proposal hasn't finished: {:?}", other),
+	}
+}
+
+#[test]
+fn finished_proposals_keep_their_results() {
+	new_test_ext().execute_with(|| {
+		setup_voter(2);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 5, Vote::Aye), (1, 2, Vote::Nay)])
+		));
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(2),
+			0,
+			BoundedVec::truncate_from(vec![(0, 3, Vote::Nay)])
+		));
+
+		next_block();
+		assert_eq!(
+			QuadraticVoting::referendum_info(0, 0),
+			Some(ProposalInfo::Finished(FinishedProposalInfo {
+				proposal_hash: BlakeTwo256::hash(&"Let's go".encode()),
+				approved: true,
+				end: 3,
+				quorum: 0,
+				tally: Tally { aye_votes: 5, nay_votes: 3, abstain_votes: 0, turnout: 8 },
+				voters: 2,
+				points: 34,
+			}))
+		);
+		let rejected = finished_info(0, 1);
+		assert!(!rejected.approved);
+		assert_eq!(rejected.voters, 1);
+		assert_eq!(rejected.points, 4);
+
+		// Votes can still be read once the proposal has finished
+		let proposal_info = QuadraticVoting::referendum_info(0, 0).unwrap();
+		assert_eq!(proposal_info.get_aye_votes(), 5);
+		assert_eq!(proposal_info.get_nay_votes(), 3);
+		assert_eq!(proposal_info.get_abstain_votes(), 0);
+	});
+}
+
+#[test]
+fn malformed_ballots_are_rejected() {
 	new_test_ext().execute_with(|| {
 		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
 		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
 		run_to_block(LaunchPeriod::get());
+		assert_noop!(
+			QuadraticVoting::submit_votes(Origin::signed(1), 0, BoundedVec::default()),
+			Error::<Test>::EmptyBallot
+		);
+		assert_noop!(
+			QuadraticVoting::submit_votes(
+				Origin::signed(1),
+				0,
+				BoundedVec::truncate_from(vec![(2, 1, Vote::Aye)])
+			),
+			Error::<Test>::ProposalNotInReferendum
+		);
+		assert_noop!(
+			QuadraticVoting::submit_votes(
+				Origin::signed(1),
+				0,
+				BoundedVec::truncate_from(vec![(0, 1, Vote::Aye), (0, 1, Vote::Nay)])
+			),
+			Error::<Test>::DuplicateVote
+		);
 		assert_noop!(
 			QuadraticVoting::submit_votes(
 				Origin::signed(1),
-				BoundedVec::truncate_from(vec![(5, Vote::Nay)])
+				0,
+				BoundedVec::truncate_from(vec![(0, 11, Vote::Aye)])
 			),
-			Error::<Test>::MissingVotes
+			Error::<Test>::TooManyVotes
+		);
+	});
+}
+
+#[test]
+fn referenda_can_overlap() {
+	new_test_ext().execute_with(|| {
+		VotingPeriod::set(&3);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "First".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Second".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Third".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Fourth".encode()));
+		run_to_block(2 * LaunchPeriod::get());
+		assert_eq!(QuadraticVoting::active_referenda().into_inner(), vec![0, 1]);
+		assert_eq!(
+			QuadraticVoting::referendum_status(0),
+			Some(ReferendumStatus::Voting { ends_at: 5 })
+		);
+		assert_eq!(
+			QuadraticVoting::referendum_status(1),
+			Some(ReferendumStatus::Voting { ends_at: 7 })
 		);
+
+		// Ballots are kept separately for each referendum
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			1,
+			BoundedVec::truncate_from(vec![(0, 2, Vote::Aye), (1, 1, Vote::Nay)])
+		));
 		assert_ok!(QuadraticVoting::submit_votes(
 			Origin::signed(1),
-			BoundedVec::truncate_from(vec![(5, Vote::Nay), (1, Vote::Nay)])
+			0,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Nay), (1, 3, Vote::Aye)])
 		));
+		assert_eq!(QuadraticVoting::referendum_info(0, 1).unwrap().get_aye_votes(), 3);
+		assert_eq!(QuadraticVoting::referendum_info(1, 0).unwrap().get_aye_votes(), 2);
+
+		run_to_block(5);
+		assert!(QuadraticVoting::referendum_info(0, 0).unwrap().has_finished());
+		assert!(QuadraticVoting::referendum_info(1, 0).unwrap().is_ongoing());
+		assert_eq!(QuadraticVoting::active_referenda().into_inner(), vec![1]);
+		run_to_block(7);
+		assert!(QuadraticVoting::referendum_info(1, 0).unwrap().has_finished());
+		assert!(QuadraticVoting::active_referenda().is_empty());
+	});
+}
+
+#[test]
+fn referenda_do_not_start_in_a_block_where_another_one_ends() {
+	new_test_ext().execute_with(|| {
+		VotingPeriod::set(&2);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "First".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Second".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Third".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Fourth".encode()));
+
+		run_to_block(2 * LaunchPeriod::get());
+		assert_eq!(
+			QuadraticVoting::referendum_status(0),
+			Some(ReferendumStatus::Closed { ended_at: 4 })
+		);
+		assert_eq!(
+			QuadraticVoting::referendum_status(1),
+			Some(ReferendumStatus::Scheduled { starts_at: 5 })
+		);
+		System::assert_has_event(pallet_quadratic_voting::Event::ReferendumScheduled(1, 5).into());
+		assert_eq!(QuadraticVoting::active_referenda().into_inner(), vec![1]);
+		assert_eq!(QuadraticVoting::queued_proposals().len(), 2);
 		assert_noop!(
 			QuadraticVoting::submit_votes(
 				Origin::signed(1),
-				BoundedVec::truncate_from(vec![(5, Vote::Nay), (1, Vote::Nay)])
+				1,
+				BoundedVec::truncate_from(vec![(0, 1, Vote::Aye)])
 			),
+			Error::<Test>::NoActiveReferendum
+		);
+
+		next_block();
+		assert_eq!(
+			QuadraticVoting::referendum_status(1),
+			Some(ReferendumStatus::Voting { ends_at: 7 })
+		);
+		assert!(QuadraticVoting::referendum_info(1, 0).unwrap().is_ongoing());
+		assert_eq!(QuadraticVoting::queued_proposals().len(), 0);
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			1,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Aye)])
+		));
+
+		run_to_block(7);
+		assert_eq!(
+			QuadraticVoting::referendum_status(1),
+			Some(ReferendumStatus::Closed { ended_at: 7 })
+		);
+		assert!(QuadraticVoting::active_referenda().is_empty());
+	});
+}
+
+#[test]
+fn scheduled_referenda_that_fail_to_start_are_cancelled() {
+	new_test_ext().execute_with(|| {
+		VotingPeriod::set(&2);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "First".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Second".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Third".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Fourth".encode()));
+		run_to_block(2 * LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::withdraw_proposal(
+			Origin::signed(1),
+			BlakeTwo256::hash(&"Third".encode())
+		));
+
+		next_block();
+		System::assert_has_event(
+			pallet_quadratic_voting::Event::ReferendumLaunchFailed(
+				1,
+				Error::<Test>::NotEnoughProposalsInQueue.into(),
+			)
+			.into(),
+		);
+		System::assert_has_event(pallet_quadratic_voting::Event::ReferendumCancelled(1).into());
+		assert_eq!(
+			QuadraticVoting::referendum_status(1),
+			Some(ReferendumStatus::Cancelled { at: 5 })
+		);
+		assert!(QuadraticVoting::active_referenda().is_empty());
+		assert_eq!(QuadraticVoting::queued_proposals().len(), 1);
+
+		// The next referendum takes the following index
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Fifth".encode()));
+		run_to_block(3 * LaunchPeriod::get());
+		assert_eq!(QuadraticVoting::active_referenda().into_inner(), vec![2]);
+	});
+}
+
+#[test]
+fn referenda_that_fail_to_be_tallied_are_cancelled() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 5, Vote::Aye)])
+		));
+		pallet_quadratic_voting::ReferendumInfo::<Test>::insert(0, 1, ProposalInfo::Cancelled(2));
+
+		next_block();
+		System::assert_has_event(
+			pallet_quadratic_voting::Event::LifecycleError(
+				0,
+				Error::<Test>::ProposalNotOngoing.into(),
+			)
+			.into(),
+		);
+		assert_eq!(
+			QuadraticVoting::referendum_status(0),
+			Some(ReferendumStatus::Cancelled { at: 3 })
+		);
+		assert!(QuadraticVoting::active_referenda().is_empty());
+		assert!(QuadraticVoting::referendum_info(0, 0).unwrap().is_cancelled());
+		assert_eq!(QuadraticVoting::voter_points(1), Some(100));
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn points_are_refunded_when_referendum_ends() {
+	new_test_ext().execute_with(|| {
+		VotingPeriod::set(&3);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "First".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Second".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Third".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Fourth".encode()));
+		run_to_block(2 * LaunchPeriod::get());
+
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 5, Vote::Aye), (1, 2, Vote::Nay)])
+		));
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			1,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Aye), (1, 3, Vote::Nay)])
+		));
+		assert_eq!(QuadraticVoting::voter_points(1), Some(61));
+
+		// Only the points of the ballot on the referendum that ended are refunded
+		run_to_block(5);
+		assert_eq!(QuadraticVoting::voter_status(&1).unwrap().points, 90);
+		run_to_block(7);
+		assert_eq!(QuadraticVoting::voter_status(&1).unwrap().points, 100);
+	});
+}
+
+#[test]
+fn points_are_reclaimed_on_the_next_ballot() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 3, Vote::Aye), (1, 1, Vote::Nay)])
+		));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Third".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Fourth".encode()));
+
+		// Ending the referendum doesn't touch the ballots
+		next_block();
+		assert_eq!(QuadraticVoting::voter_points(1), Some(90));
+		assert_eq!(QuadraticVoting::voter_status(&1).unwrap().points, 100);
+		assert_eq!(QuadraticVoting::pending_refunds_of(1).unwrap().into_inner(), vec![0]);
+
+		next_block();
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			1,
+			BoundedVec::truncate_from(vec![(0, 7, Vote::Aye), (1, 7, Vote::Nay)])
+		));
+		assert_eq!(QuadraticVoting::voter_points(1), Some(2));
+		assert_eq!(QuadraticVoting::pending_refunds_of(1).unwrap().into_inner(), vec![1]);
+	});
+}
+
+#[test]
+fn points_are_refunded_when_ballots_are_pruned() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 3, Vote::Aye), (1, 1, Vote::Nay)])
+		));
+		next_block();
+		assert_eq!(QuadraticVoting::voter_points(1), Some(90));
+
+		QuadraticVoting::on_idle(100, Weight::MAX);
+		assert_eq!(QuadraticVoting::voting_of(0, 1), None);
+		assert_eq!(QuadraticVoting::voter_points(1), Some(100));
+		assert_eq!(QuadraticVoting::pending_refunds_of(1), None);
+	});
+}
+
+#[test]
+fn ballots_are_recorded_even_when_they_cost_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		let votes = BoundedVec::truncate_from(vec![(0, 0, Vote::Aye), (1, 0, Vote::Nay)]);
+		assert_ok!(QuadraticVoting::submit_votes(Origin::signed(1), 0, votes.clone()));
+		assert_eq!(QuadraticVoting::voter_points(1), Some(100));
+		assert_eq!(
+			QuadraticVoting::voting_of(0, 1),
+			Some(Ballot {
+				votes: votes.clone(),
+				points: 0,
+				conviction: Conviction::None,
+				balance: 0
+			})
+		);
+		assert_noop!(
+			QuadraticVoting::submit_votes(Origin::signed(1), 0, votes),
 			Error::<Test>::AlreadyVoted
 		);
 	});
 }
+
+#[test]
+fn past_ballots_can_be_looked_up() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_eq!(QuadraticVoting::ballots_of(&1), vec![]);
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 3, Vote::Aye), (1, 1, Vote::Nay)])
+		));
+		next_block();
+		assert!(QuadraticVoting::referendum_info(0, 0).unwrap().has_finished());
+
+		let ballot = Ballot {
+			votes: vec![(0, 3, Vote::Aye), (1, 1, Vote::Nay)],
+			points: 10,
+			conviction: Conviction::None,
+			balance: 0,
+		};
+		assert_eq!(QuadraticVoting::ballots_of(&1), vec![(0, ballot)]);
+		assert_eq!(QuadraticVoting::ballots_of(&2), vec![]);
+	});
+}
+
+#[test]
+fn ballot_can_be_changed_while_referendum_is_ongoing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_noop!(
+			QuadraticVoting::change_votes(
+				Origin::signed(1),
+				0,
+				BoundedVec::truncate_from(vec![(0, 1, Vote::Aye), (1, 1, Vote::Aye)])
+			),
+			Error::<Test>::NotVoted
+		);
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 5, Vote::Aye), (1, 5, Vote::Nay)])
+		));
+		assert_eq!(QuadraticVoting::voter_points(1), Some(50));
+
+		// The points of the old ballot can be spent on the new one
+		assert_ok!(QuadraticVoting::change_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 2, Vote::Nay), (1, 9, Vote::Aye)])
+		));
+		assert_eq!(QuadraticVoting::voter_points(1), Some(15));
+		let first = QuadraticVoting::referendum_info(0, 0).unwrap();
+		assert_eq!((first.get_aye_votes(), first.get_nay_votes()), (0, 2));
+		let second = QuadraticVoting::referendum_info(0, 1).unwrap();
+		assert_eq!((second.get_aye_votes(), second.get_nay_votes()), (9, 0));
+
+		assert_noop!(
+			QuadraticVoting::change_votes(
+				Origin::signed(1),
+				0,
+				BoundedVec::truncate_from(vec![(0, 10, Vote::Aye), (1, 1, Vote::Aye)])
+			),
+			Error::<Test>::NotEnoughPoints
+		);
+
+		next_block();
+		assert_eq!(QuadraticVoting::voter_status(&1).unwrap().points, 100);
+		assert_noop!(
+			QuadraticVoting::change_votes(
+				Origin::signed(1),
+				0,
+				BoundedVec::truncate_from(vec![(0, 1, Vote::Aye), (1, 1, Vote::Aye)])
+			),
+			Error::<Test>::NoActiveReferendum
+		);
+	});
+}
+
+#[test]
+fn ballot_can_be_removed_while_referendum_is_ongoing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_noop!(QuadraticVoting::remove_votes(Origin::signed(1), 0), Error::<Test>::NotVoted);
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 3, Vote::Aye), (1, 4, Vote::Nay)])
+		));
+
+		assert_ok!(QuadraticVoting::remove_votes(Origin::signed(1), 0));
+		assert_eq!(QuadraticVoting::voter_points(1), Some(100));
+		assert_eq!(QuadraticVoting::voting_of(0, 1), None);
+		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_aye_votes(), 0);
+		assert_eq!(QuadraticVoting::referendum_info(0, 1).unwrap().get_nay_votes(), 0);
+
+		// A new ballot can be submitted afterwards
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Nay), (1, 1, Vote::Aye)])
+		));
+		next_block();
+		assert_noop!(
+			QuadraticVoting::remove_votes(Origin::signed(1), 0),
+			Error::<Test>::NoActiveReferendum
+		);
+		assert_eq!(QuadraticVoting::voter_status(&1).unwrap().points, 100);
+	});
+}
+
+#[test]
+fn conviction_multiplies_votes_and_locks_balance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes_with_conviction(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 2, Vote::Aye), (1, 1, Vote::Nay)]),
+			Conviction::Locked3x,
+			500
+		));
+		// Points are spent on the votes before the conviction multiplies them
+		assert_eq!(QuadraticVoting::voter_points(1), Some(95));
+		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_aye_votes(), 6);
+		assert_eq!(QuadraticVoting::referendum_info(0, 1).unwrap().get_nay_votes(), 3);
+
+		// The referendum ends at block 3, then the balance is locked for two periods of 4 blocks
+		assert_eq!(QuadraticVoting::conviction_lock_of(1).unwrap().unlock_at, 11);
+		next_block();
+		assert_noop!(
+			Balances::transfer(Origin::signed(1), 2, 600),
+			pallet_balances::Error::<Test>::LiquidityRestrictions
+		);
+		assert_noop!(QuadraticVoting::unlock(Origin::signed(2), 1), Error::<Test>::LockNotExpired);
+
+		run_to_block(11);
+		assert_ok!(QuadraticVoting::unlock(Origin::signed(2), 1));
+		System::assert_last_event(pallet_quadratic_voting::Event::Unlocked(1, 500).into());
+		assert_eq!(QuadraticVoting::conviction_lock_of(1), None);
+		assert_ok!(Balances::transfer(Origin::signed(1), 2, 600));
+	});
+}
+
+#[test]
+fn conviction_needs_a_lockable_balance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		let votes: pallet_quadratic_voting::ReferendumVotes<Test> =
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Aye)]);
+		assert_noop!(
+			QuadraticVoting::submit_votes_with_conviction(
+				Origin::signed(1),
+				0,
+				votes.clone(),
+				Conviction::Locked2x,
+				0
+			),
+			Error::<Test>::NoConvictionBalance
+		);
+		assert_noop!(
+			QuadraticVoting::submit_votes_with_conviction(
+				Origin::signed(1),
+				0,
+				votes.clone(),
+				Conviction::Locked2x,
+				2000
+			),
+			Error::<Test>::InsufficientFunds
+		);
+		assert_noop!(QuadraticVoting::unlock(Origin::signed(1), 1), Error::<Test>::NotLocked);
+
+		// Without conviction nothing is locked
+		assert_ok!(QuadraticVoting::submit_votes_with_conviction(
+			Origin::signed(1),
+			0,
+			votes,
+			Conviction::None,
+			500
+		));
+		assert_eq!(QuadraticVoting::conviction_lock_of(1), None);
+		assert_eq!(QuadraticVoting::voting_of(0, 1).unwrap().balance, 0);
+	});
+}
+
+#[test]
+fn tiny_locks_do_not_get_the_conviction_multiplier() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		// Each vote needs `MinConvictionBalance` (10) locked
+		assert_noop!(
+			QuadraticVoting::submit_votes_with_conviction(
+				Origin::signed(1),
+				0,
+				BoundedVec::truncate_from(vec![(0, 5, Vote::Aye)]),
+				Conviction::Locked6x,
+				1
+			),
+			Error::<Test>::ConvictionBalanceTooLow
+		);
+		assert_noop!(
+			QuadraticVoting::submit_votes_with_conviction(
+				Origin::signed(1),
+				0,
+				BoundedVec::truncate_from(vec![(0, 5, Vote::Aye)]),
+				Conviction::Locked6x,
+				49
+			),
+			Error::<Test>::ConvictionBalanceTooLow
+		);
+
+		assert_ok!(QuadraticVoting::submit_votes_with_conviction(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 5, Vote::Aye)]),
+			Conviction::Locked6x,
+			50
+		));
+		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_aye_votes(), 30);
+
+		// Changed ballots can't add votes the lock doesn't cover
+		assert_noop!(
+			QuadraticVoting::change_votes(
+				Origin::signed(1),
+				0,
+				BoundedVec::truncate_from(vec![(0, 5, Vote::Aye), (1, 1, Vote::Nay)])
+			),
+			Error::<Test>::ConvictionBalanceTooLow
+		);
+		assert_ok!(QuadraticVoting::change_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 4, Vote::Aye), (1, 1, Vote::Nay)])
+		));
+	});
+}
+
+#[test]
+fn changed_ballots_keep_their_conviction() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes_with_conviction(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 2, Vote::Aye)]),
+			Conviction::Locked2x,
+			100
+		));
+		assert_ok!(QuadraticVoting::change_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(1, 3, Vote::Nay)])
+		));
+		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_aye_votes(), 0);
+		assert_eq!(QuadraticVoting::referendum_info(0, 1).unwrap().get_nay_votes(), 6);
+
+		assert_ok!(QuadraticVoting::remove_votes(Origin::signed(1), 0));
+		assert_eq!(QuadraticVoting::referendum_info(0, 1).unwrap().get_nay_votes(), 0);
+		assert_eq!(QuadraticVoting::voter_points(1), Some(100));
+	});
+}
+
+fn setup_voter(who: u64) {
+	assert_ok!(Identity::create_identity(Origin::root(), who, Default::default()));
+	assert_ok!(QuadraticVoting::register_voter(Origin::signed(who), who));
+}
+
+#[test]
+fn only_the_account_or_registration_origin_can_register_it() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::create_identity(Origin::root(), 2, Default::default()));
+		assert_noop!(QuadraticVoting::register_voter(Origin::signed(1), 2), BadOrigin);
+		assert_noop!(QuadraticVoting::register_voter(Origin::none(), 2), BadOrigin);
+		assert_ok!(QuadraticVoting::register_voter(Origin::root(), 2));
+		assert_eq!(QuadraticVoting::voter_points(2), Some(100));
+
+		assert_noop!(QuadraticVoting::deregister_voter(Origin::signed(1), 2), BadOrigin);
+		assert_ok!(QuadraticVoting::deregister_voter(Origin::signed(2), 2));
+		System::assert_last_event(pallet_quadratic_voting::Event::VoterDeregistered(2).into());
+		assert_eq!(QuadraticVoting::voter_points(2), None);
+		assert_eq!(QuadraticVoting::voter_count(), 1);
+		assert_noop!(
+			QuadraticVoting::deregister_voter(Origin::root(), 2),
+			Error::<Test>::NotAVoter
+		);
+	});
+}
+
+#[test]
+fn deregistering_retracts_pending_ballots() {
+	new_test_ext().execute_with(|| {
+		setup_voter(2);
+		setup_voter(3);
+		assert_ok!(QuadraticVoting::delegate(Origin::signed(2), 1));
+		assert_ok!(QuadraticVoting::delegate(Origin::signed(1), 3));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 3, Vote::Aye)])
+		));
+		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_aye_votes(), 6);
+
+		let post_info = QuadraticVoting::deregister_voter(Origin::root(), 1).unwrap();
+		// Only the referendum it voted on and its single delegator are charged for
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::deregister_voter(1, 1)));
+		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_aye_votes(), 0);
+		assert_eq!(QuadraticVoting::voting_of(0, 1), None);
+		assert_eq!(QuadraticVoting::voting_of(0, 2), None);
+		assert_eq!(QuadraticVoting::voter_points(2), Some(100));
+		assert_eq!(QuadraticVoting::delegation_of(1), None);
+		assert_eq!(QuadraticVoting::delegation_of(2), None);
+		assert!(QuadraticVoting::delegators_of(3).is_empty());
+		assert_noop!(
+			QuadraticVoting::submit_votes(
+				Origin::signed(1),
+				0,
+				BoundedVec::truncate_from(vec![(0, 1, Vote::Aye)])
+			),
+			Error::<Test>::NotAVoter
+		);
+	});
+}
+
+#[test]
+fn voters_are_deregistered_when_their_identity_is_deleted() {
+	new_test_ext().execute_with(|| {
+		setup_voter(2);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(2),
+			0,
+			BoundedVec::truncate_from(vec![(0, 4, Vote::Nay)])
+		));
+
+		assert_ok!(Identity::delete_identity(Origin::root(), 2));
+		System::assert_has_event(pallet_quadratic_voting::Event::VoterDeregistered(2).into());
+		assert_eq!(QuadraticVoting::voter_points(2), None);
+		assert_eq!(QuadraticVoting::voting_of(0, 2), None);
+		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_nay_votes(), 0);
+
+		// Accounts that aren't voters are left alone
+		assert_ok!(Identity::create_identity(Origin::root(), 3, Default::default()));
+		assert_ok!(Identity::delete_identity(Origin::root(), 3));
+		assert_eq!(QuadraticVoting::voter_count(), 1);
+	});
+}
+
+#[test]
+fn delegates_vote_with_their_delegators_points() {
+	new_test_ext().execute_with(|| {
+		setup_voter(2);
+		setup_voter(3);
+		assert_ok!(QuadraticVoting::delegate(Origin::signed(2), 1));
+		assert_ok!(QuadraticVoting::delegate(Origin::signed(3), 1));
+		assert_eq!(QuadraticVoting::delegators_of(1).into_inner(), vec![2, 3]);
+		pallet_quadratic_voting::VoterPoints::<Test>::insert(3, 3);
+
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		let post_info = QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 3, Vote::Aye), (1, 1, Vote::Nay)]),
+		)
+		.unwrap();
+		// Only the two delegators of 1 are charged for, not `MaxDelegators`
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::submit_votes(2, 2)));
+
+		// Each delegator pays for their own share, 3 can't afford it and is left out
+		assert_eq!(QuadraticVoting::voter_points(1), Some(90));
+		assert_eq!(QuadraticVoting::voter_points(2), Some(90));
+		assert_eq!(QuadraticVoting::voter_points(3), Some(3));
+		assert_eq!(QuadraticVoting::delegated_ballot_of(0, 2), Some(1));
+		assert_eq!(QuadraticVoting::voting_of(0, 3), None);
+		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_aye_votes(), 6);
+		assert_eq!(QuadraticVoting::referendum_info(0, 1).unwrap().get_nay_votes(), 2);
+
+		// Delegated ballots follow the delegate's changes
+		let post_info = QuadraticVoting::change_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(1, 2, Vote::Aye)]),
+		)
+		.unwrap();
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::change_votes(1, 2, 2)));
+		assert_eq!(QuadraticVoting::voter_points(2), Some(96));
+		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_aye_votes(), 0);
+		assert_eq!(QuadraticVoting::referendum_info(0, 1).unwrap().get_aye_votes(), 4);
+		assert_ok!(QuadraticVoting::remove_votes(Origin::signed(1), 0));
+		assert_eq!(QuadraticVoting::voter_points(2), Some(100));
+		assert_eq!(QuadraticVoting::referendum_info(0, 1).unwrap().get_aye_votes(), 0);
+
+		// Points of delegated ballots are refunded when the referendum ends
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Aye)])
+		));
+		assert_eq!(QuadraticVoting::voter_points(2), Some(99));
+		next_block();
+		assert_eq!(QuadraticVoting::voter_status(&2).unwrap().points, 100);
+	});
+}
+
+#[test]
+fn delegating_applies_the_ballots_the_delegate_already_cast() {
+	new_test_ext().execute_with(|| {
+		setup_voter(2);
+		setup_voter(3);
+		setup_voter(4);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 3, Vote::Aye), (1, 1, Vote::Nay)])
+		));
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(3),
+			0,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Nay)])
+		));
+
+		assert_ok!(QuadraticVoting::delegate(Origin::signed(2), 1));
+		assert_eq!(QuadraticVoting::voter_points(2), Some(90));
+		assert_eq!(QuadraticVoting::delegated_ballot_of(0, 2), Some(1));
+		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_aye_votes(), 6);
+		assert_eq!(QuadraticVoting::referendum_info(0, 1).unwrap().get_nay_votes(), 2);
+
+		// A direct ballot is kept, and delegated ballots are not passed on
+		assert_ok!(QuadraticVoting::delegate(Origin::signed(3), 1));
+		assert_eq!(QuadraticVoting::voting_of(0, 3).unwrap().points, 1);
+		assert_ok!(QuadraticVoting::delegate(Origin::signed(4), 2));
+		assert_eq!(QuadraticVoting::voting_of(0, 4), None);
+
+		// Later changes of the delegate are followed as usual
+		assert_ok!(QuadraticVoting::remove_votes(Origin::signed(1), 0));
+		assert_eq!(QuadraticVoting::voter_points(2), Some(100));
+		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_aye_votes(), 0);
+	});
+}
+
+#[test]
+fn voting_directly_overrides_the_delegate() {
+	new_test_ext().execute_with(|| {
+		setup_voter(2);
+		assert_ok!(QuadraticVoting::delegate(Origin::signed(2), 1));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 3, Vote::Aye), (1, 1, Vote::Nay)])
+		));
+
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(2),
+			0,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Nay)])
+		));
+		assert_eq!(QuadraticVoting::voter_points(2), Some(99));
+		assert_eq!(QuadraticVoting::delegated_ballot_of(0, 2), None);
+		let first = QuadraticVoting::referendum_info(0, 0).unwrap();
+		assert_eq!((first.get_aye_votes(), first.get_nay_votes()), (3, 1));
+		assert_eq!(QuadraticVoting::referendum_info(0, 1).unwrap().get_nay_votes(), 1);
+
+		// The direct ballot is no longer touched by the delegate
+		assert_ok!(QuadraticVoting::remove_votes(Origin::signed(1), 0));
+		assert_eq!(QuadraticVoting::voting_of(0, 2).unwrap().points, 1);
+		assert_noop!(
+			QuadraticVoting::submit_votes(
+				Origin::signed(2),
+				0,
+				BoundedVec::truncate_from(vec![(0, 1, Vote::Nay)])
+			),
+			Error::<Test>::AlreadyVoted
+		);
+	});
+}
+
+#[test]
+fn delegation_can_be_revoked() {
+	new_test_ext().execute_with(|| {
+		setup_voter(2);
+		assert_noop!(QuadraticVoting::undelegate(Origin::signed(2)), Error::<Test>::NotDelegating);
+		assert_ok!(QuadraticVoting::delegate(Origin::signed(2), 1));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 3, Vote::Aye)])
+		));
+		assert_eq!(QuadraticVoting::voter_points(2), Some(91));
+
+		assert_ok!(QuadraticVoting::undelegate(Origin::signed(2)));
+		System::assert_last_event(pallet_quadratic_voting::Event::Undelegated(2).into());
+		assert_eq!(QuadraticVoting::voter_points(2), Some(100));
+		assert_eq!(QuadraticVoting::voting_of(0, 2), None);
+		assert_eq!(QuadraticVoting::referendum_info(0, 0).unwrap().get_aye_votes(), 3);
+		assert_eq!(QuadraticVoting::delegation_of(2), None);
+		assert!(QuadraticVoting::delegators_of(1).is_empty());
+	});
+}
+
+#[test]
+fn delegation_is_checked() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			QuadraticVoting::delegate(Origin::signed(1), 1),
+			Error::<Test>::CannotDelegateToSelf
+		);
+		assert_noop!(QuadraticVoting::delegate(Origin::signed(1), 2), Error::<Test>::NotAVoter);
+		assert_noop!(QuadraticVoting::delegate(Origin::signed(2), 1), Error::<Test>::NotAVoter);
+
+		for delegator in 2..=4 {
+			setup_voter(delegator);
+			assert_ok!(QuadraticVoting::delegate(Origin::signed(delegator), 1));
+		}
+		assert_noop!(
+			QuadraticVoting::delegate(Origin::signed(2), 3),
+			Error::<Test>::AlreadyDelegating
+		);
+		setup_voter(5);
+		assert_noop!(
+			QuadraticVoting::delegate(Origin::signed(5), 1),
+			Error::<Test>::TooManyDelegators
+		);
+	});
+}
+
+#[test]
+fn cannot_vote_on_inactive_referendum() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_noop!(
+			QuadraticVoting::submit_votes(
+				Origin::signed(1),
+				1,
+				BoundedVec::truncate_from(vec![(0, 1, Vote::Nay), (1, 1, Vote::Aye)])
+			),
+			Error::<Test>::NoActiveReferendum
+		);
+	});
+}
+
+#[test]
+fn submitting_a_proposal_reserves_a_deposit() {
+	new_test_ext().execute_with(|| {
+		let proposal = "Should we buy DOT?".encode();
+		let deposit = QuadraticVoting::proposal_deposit(proposal.len() as u32);
+		assert_eq!(deposit, 10 + proposal.len() as u64);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), proposal));
+		assert_eq!(Balances::reserved_balance(1), deposit);
+		assert_eq!(QuadraticVoting::queued_proposals()[0].deposit, deposit);
+		assert_eq!(QuadraticVoting::queued_proposals()[0].proposer, 1);
+	});
+}
+
+#[test]
+fn deposits_are_returned_when_referendum_ends() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert!(QuadraticVoting::proposal_deposit_of(0, 0).is_some());
+		assert!(Balances::reserved_balance(1) > 0);
+		next_block();
+		assert_eq!(QuadraticVoting::proposal_deposit_of(0, 0), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000);
+	});
+}
+
+#[test]
+fn moderator_can_slash_spam_proposals() {
+	new_test_ext().execute_with(|| {
+		let proposal_hash = BlakeTwo256::hash(&"Buy my NFT".encode());
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Buy my NFT".encode()));
+		let deposit = QuadraticVoting::queued_proposals()[0].deposit;
+		setup_voter(2);
+		assert_ok!(QuadraticVoting::second(Origin::signed(2), proposal_hash));
+		assert_noop!(QuadraticVoting::slash_proposal(Origin::signed(1), proposal_hash), BadOrigin);
+		assert_noop!(
+			QuadraticVoting::slash_proposal(
+				Origin::root(),
+				BlakeTwo256::hash(&"Let's go".encode())
+			),
+			Error::<Test>::ProposalNotInQueue
+		);
+		assert_ok!(QuadraticVoting::slash_proposal(Origin::root(), proposal_hash));
+		assert!(QuadraticVoting::queued_proposals().is_empty());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000 - deposit);
+		// Seconding a spam proposal is not punished
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn proposers_can_withdraw_their_proposals() {
+	new_test_ext().execute_with(|| {
+		let proposal_hash = BlakeTwo256::hash(&"Let's go".encode());
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		setup_voter(2);
+		assert_ok!(QuadraticVoting::second(Origin::signed(2), proposal_hash));
+		assert_noop!(
+			QuadraticVoting::withdraw_proposal(Origin::signed(2), proposal_hash),
+			Error::<Test>::NotProposer
+		);
+		assert_noop!(
+			QuadraticVoting::withdraw_proposal(
+				Origin::signed(1),
+				BlakeTwo256::hash(&"Let's goo".encode())
+			),
+			Error::<Test>::ProposalNotInQueue
+		);
+
+		assert_ok!(QuadraticVoting::withdraw_proposal(Origin::signed(1), proposal_hash));
+		System::assert_last_event(
+			pallet_quadratic_voting::Event::ProposalWithdrawn(proposal_hash, 1).into(),
+		);
+		assert!(QuadraticVoting::queued_proposals().is_empty());
+		assert_eq!(QuadraticVoting::proposal_preimages(proposal_hash), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn moderator_can_remove_proposals_without_slashing() {
+	new_test_ext().execute_with(|| {
+		let proposal_hash = BlakeTwo256::hash(&"Let's go".encode());
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_noop!(QuadraticVoting::remove_proposal(Origin::signed(1), proposal_hash), BadOrigin);
+		assert_noop!(
+			QuadraticVoting::remove_proposal(
+				Origin::root(),
+				BlakeTwo256::hash(&"Let's goo".encode())
+			),
+			Error::<Test>::ProposalNotInQueue
+		);
+
+		assert_ok!(QuadraticVoting::remove_proposal(Origin::root(), proposal_hash));
+		System::assert_last_event(
+			pallet_quadratic_voting::Event::ProposalRemoved(proposal_hash).into(),
+		);
+		assert!(QuadraticVoting::queued_proposals().is_empty());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000);
+	});
+}
+
+#[test]
+fn queued_proposals_are_identified_by_their_hash() {
+	new_test_ext().execute_with(|| {
+		ProposalQueueSize::set(&3);
+		let go = BlakeTwo256::hash(&"Let's go".encode());
+		let gooo = BlakeTwo256::hash(&"Let's gooo".encode());
+		for proposal in ["Let's go", "Let's goo", "Let's gooo"] {
+			assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), proposal.encode()));
+		}
+		assert_noop!(
+			QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()),
+			Error::<Test>::ProposalAlreadyQueued
+		);
+
+		// Removing a proposal shifts the ones after it, which are still found by their hash
+		assert_ok!(QuadraticVoting::withdraw_proposal(Origin::signed(1), go));
+		assert_ok!(QuadraticVoting::remove_proposal(Origin::root(), gooo));
+		System::assert_last_event(pallet_quadratic_voting::Event::ProposalRemoved(gooo).into());
+		assert_eq!(QuadraticVoting::queued_proposals().len(), 1);
+		assert_eq!(
+			QuadraticVoting::queued_proposals()[0].proposal_hash,
+			BlakeTwo256::hash(&"Let's goo".encode())
+		);
+	});
+}
+
+#[test]
+fn referenda_can_be_cancelled() {
+	new_test_ext().execute_with(|| {
+		let proposal_hash = BlakeTwo256::hash(&"Let's go".encode());
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 5, Vote::Aye), (1, 2, Vote::Nay)])
+		));
+		assert_eq!(QuadraticVoting::voter_points(1), Some(71));
+
+		assert_noop!(QuadraticVoting::cancel_referendum(Origin::signed(1), 0, 1), BadOrigin);
+		assert_noop!(
+			QuadraticVoting::cancel_referendum(Origin::root(), 1, 1),
+			Error::<Test>::NoActiveReferendum
+		);
+		assert_noop!(
+			QuadraticVoting::cancel_referendum(Origin::root(), 0, 0),
+			Error::<Test>::InvalidWitness
+		);
+
+		assert_ok!(QuadraticVoting::cancel_referendum(Origin::root(), 0, 1));
+		System::assert_last_event(pallet_quadratic_voting::Event::ReferendumCancelled(0).into());
+		assert!(QuadraticVoting::active_referenda().is_empty());
+		assert!(QuadraticVoting::referendum_info(0, 0).unwrap().is_cancelled());
+		assert!(!QuadraticVoting::referendum_info(0, 1).unwrap().has_finished());
+		assert_eq!(QuadraticVoting::voter_points(1), Some(100));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(QuadraticVoting::proposal_preimages(proposal_hash), None);
+
+		// The referendum doesn't end again
+		run_to_block(LaunchPeriod::get() + VotingPeriod::get());
+		assert!(QuadraticVoting::referendum_info(0, 0).unwrap().is_cancelled());
+		assert_eq!(QuadraticVoting::voter_points(1), Some(100));
+	});
+}
+
+#[test]
+fn vetoed_proposals_are_blacklisted_for_a_cooldown() {
+	new_test_ext().execute_with(|| {
+		let proposal_hash = BlakeTwo256::hash(&"Let's go".encode());
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_noop!(QuadraticVoting::veto(Origin::signed(1), 0, 0, 0), BadOrigin);
+		assert_noop!(
+			QuadraticVoting::veto(Origin::root(), 0, 2, 0),
+			Error::<Test>::NoActiveReferendum
+		);
+
+		assert_ok!(QuadraticVoting::veto(Origin::root(), 0, 0, 0));
+		let until = LaunchPeriod::get() + 20;
+		System::assert_last_event(
+			pallet_quadratic_voting::Event::ProposalVetoed(0, proposal_hash, until).into(),
+		);
+		assert!(QuadraticVoting::active_referenda().is_empty());
+		assert_eq!(QuadraticVoting::blacklisted_until(proposal_hash), Some(until));
+		assert_noop!(
+			QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()),
+			Error::<Test>::ProposalBlacklisted
+		);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+
+		// The entry is removed once the cooldown ends
+		run_to_block(until - 1);
+		assert_eq!(QuadraticVoting::blacklisted_until(proposal_hash), Some(until));
+		next_block();
+		assert_eq!(QuadraticVoting::blacklisted_until(proposal_hash), None);
+		assert_eq!(pallet_quadratic_voting::BlacklistExpiries::<Test>::iter().count(), 0);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_eq!(QuadraticVoting::blacklisted_until(proposal_hash), None);
+	});
+}
+
+#[test]
+fn most_seconded_proposals_are_picked_first() {
+	new_test_ext().execute_with(|| {
+		ProposalQueueSize::set(&3);
+		setup_voter(2);
+		for proposal in ["Let's go", "Let's goo", "Let's gooo"] {
+			assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), proposal.encode()));
+		}
+		let gooo = BlakeTwo256::hash(&"Let's gooo".encode());
+		assert_ok!(QuadraticVoting::second(Origin::signed(1), gooo));
+		assert_ok!(QuadraticVoting::second(Origin::signed(2), gooo));
+		assert_ok!(QuadraticVoting::second(
+			Origin::signed(2),
+			BlakeTwo256::hash(&"Let's goo".encode())
+		));
+		System::assert_last_event(
+			pallet_quadratic_voting::Event::Seconded(BlakeTwo256::hash(&"Let's goo".encode()), 2)
+				.into(),
+		);
+		assert_eq!(Balances::reserved_balance(2), 10);
+		assert_eq!(QuadraticVoting::queued_proposals()[2].seconds, 2);
+		assert_eq!(QuadraticVoting::second_deposit_of(gooo, 2), Some(5));
+
+		run_to_block(LaunchPeriod::get());
+		let details = QuadraticVoting::referendum_details(0).unwrap();
+		assert_eq!(details.proposals[0].proposal_hash, BlakeTwo256::hash(&"Let's gooo".encode()));
+		assert_eq!(details.proposals[1].proposal_hash, BlakeTwo256::hash(&"Let's goo".encode()));
+		assert_eq!(
+			QuadraticVoting::queued_proposals()[0].proposal_hash,
+			BlakeTwo256::hash(&"Let's go".encode())
+		);
+		// Seconds are refunded once their proposal is picked
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(QuadraticVoting::second_deposit_of(gooo, 2), None);
+	});
+}
+
+#[test]
+fn seconding_is_checked() {
+	new_test_ext().execute_with(|| {
+		let proposal_hash = BlakeTwo256::hash(&"Let's go".encode());
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_noop!(
+			QuadraticVoting::second(Origin::signed(2), proposal_hash),
+			Error::<Test>::NotAVoter
+		);
+		assert_noop!(
+			QuadraticVoting::second(Origin::signed(1), BlakeTwo256::hash(&"Let's goo".encode())),
+			Error::<Test>::ProposalNotInQueue
+		);
+		assert_ok!(QuadraticVoting::second(Origin::signed(1), proposal_hash));
+		assert_noop!(
+			QuadraticVoting::second(Origin::signed(1), proposal_hash),
+			Error::<Test>::AlreadySeconded
+		);
+		setup_voter(2);
+		setup_voter(3);
+		assert_ok!(QuadraticVoting::second(Origin::signed(2), proposal_hash));
+		assert_noop!(
+			QuadraticVoting::second(Origin::signed(3), proposal_hash),
+			Error::<Test>::TooManySeconds
+		);
+	});
+}
+
+#[test]
+fn queued_proposals_expire() {
+	new_test_ext().execute_with(|| {
+		let proposal_hash = BlakeTwo256::hash(&"Let's go".encode());
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		setup_voter(2);
+		assert_ok!(QuadraticVoting::second(Origin::signed(2), proposal_hash));
+		assert_eq!(QuadraticVoting::queued_proposals()[0].expires_at, 11);
+
+		run_to_block(10);
+		assert_eq!(QuadraticVoting::queued_proposals().len(), 1);
+		run_to_block(12);
+		assert!(QuadraticVoting::queued_proposals().is_empty());
+		System::assert_has_event(
+			pallet_quadratic_voting::Event::ProposalExpired(proposal_hash).into(),
+		);
+		assert_eq!(QuadraticVoting::proposal_preimages(proposal_hash), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn only_a_few_proposals_expire_per_launch() {
+	new_test_ext().execute_with(|| {
+		MaxExpiredProposals::set(&1);
+		VotingPeriod::set(&20);
+		ProposalQueueSize::set(&6);
+		// The first four proposals fill both active referenda, the last two are left to expire
+		for proposal in ["a", "b", "c", "d", "Let's go", "Let's goo"] {
+			assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), proposal.encode()));
+		}
+		run_to_block(4);
+		let proposal_hash = BlakeTwo256::hash(&"Let's go".encode());
+		setup_voter(2);
+		assert_ok!(QuadraticVoting::second(Origin::signed(2), proposal_hash));
+		assert_eq!(QuadraticVoting::second_deposit_of(proposal_hash, 2), Some(5));
+
+		run_to_block(10);
+		assert_eq!(QuadraticVoting::active_referenda().len(), 2);
+		assert_eq!(QuadraticVoting::queued_proposals().len(), 2);
+
+		run_to_block(12);
+		assert_eq!(QuadraticVoting::queued_proposals().len(), 1);
+		assert_eq!(
+			QuadraticVoting::queued_proposals()[0].proposal_hash,
+			BlakeTwo256::hash(&"Let's goo".encode())
+		);
+		assert_eq!(QuadraticVoting::second_deposit_of(proposal_hash, 2), None);
+		assert_eq!(Balances::reserved_balance(2), 0);
+
+		run_to_block(14);
+		assert!(QuadraticVoting::queued_proposals().is_empty());
+	});
+}
+
+#[test]
+fn proposal_needs_enough_balance_for_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Balances::set_balance(Origin::root(), 1, 5, 0));
+		assert_noop!(
+			QuadraticVoting::submit_proposal(Origin::signed(1), "Free lunch?".encode()),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn preimages_are_cleared_when_referendum_ends() {
+	new_test_ext().execute_with(|| {
+		let proposal_hash = BlakeTwo256::hash(&"Let's go".encode());
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		assert_eq!(QuadraticVoting::proposal_preimages(proposal_hash).unwrap().references, 1);
+		run_to_block(LaunchPeriod::get());
+		assert_eq!(QuadraticVoting::proposal_preimages(proposal_hash).unwrap().references, 1);
+		next_block();
+		assert_eq!(QuadraticVoting::proposal_preimages(proposal_hash), None);
+	});
+}
+
+#[test]
+fn submit_proposal_by_noted_preimage() {
+	new_test_ext().execute_with(|| {
+		let proposal = "Should we buy DOT?".encode();
+		let proposal_hash = BlakeTwo256::hash(&proposal);
+		assert_noop!(
+			QuadraticVoting::submit_proposal_hash(Origin::signed(1), proposal_hash),
+			Error::<Test>::PreimageMissing
+		);
+		assert_ok!(QuadraticVoting::note_preimage(Origin::signed(1), proposal.clone()));
+		assert_eq!(Balances::reserved_balance(1), 2 * proposal.len() as u64);
+		assert_noop!(
+			QuadraticVoting::note_preimage(Origin::signed(1), proposal.clone()),
+			Error::<Test>::PreimageAlreadyNoted
+		);
+		assert_ok!(QuadraticVoting::submit_proposal_hash(Origin::signed(1), proposal_hash));
+		assert_eq!(QuadraticVoting::queued_proposals()[0].proposal_hash, proposal_hash);
+		assert_eq!(QuadraticVoting::proposal_preimages(proposal_hash).unwrap().references, 1);
+		assert_noop!(
+			QuadraticVoting::clear_preimage(Origin::signed(1), proposal_hash),
+			Error::<Test>::PreimageInUse
+		);
+		// Slashing the proposal drops the last reference, returning the preimage deposit
+		assert_ok!(QuadraticVoting::slash_proposal(Origin::root(), proposal_hash));
+		assert_eq!(QuadraticVoting::proposal_preimages(proposal_hash), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn unused_preimage_can_be_cleared_by_its_provider() {
+	new_test_ext().execute_with(|| {
+		let proposal = "Should we buy DOT?".encode();
+		let proposal_hash = BlakeTwo256::hash(&proposal);
+		assert_ok!(QuadraticVoting::note_preimage(Origin::signed(1), proposal));
+		assert_noop!(
+			QuadraticVoting::clear_preimage(Origin::signed(2), proposal_hash),
+			Error::<Test>::NotPreimageProvider
+		);
+		assert_ok!(QuadraticVoting::clear_preimage(Origin::signed(1), proposal_hash));
+		assert_eq!(QuadraticVoting::proposal_preimages(proposal_hash), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+fn create_identity(who: u64) -> Box<Call> {
+	Box::new(Call::Identity(pallet_basic_identity::Call::create_identity {
+		who,
+		name: H256::repeat_byte(1),
+	}))
+}
+
+#[test]
+fn approved_call_proposals_are_dispatched() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_call_proposal(Origin::signed(1), create_identity(3)));
+		assert_ok!(QuadraticVoting::submit_call_proposal(Origin::signed(1), create_identity(4)));
+		assert_eq!(QuadraticVoting::queued_proposals()[0].kind, ProposalKind::Call);
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Aye), (1, 1, Vote::Nay)])
+		));
+		next_block();
+
+		assert_eq!(Identity::identities(3), Some(H256::repeat_byte(1)));
+		assert_eq!(Identity::identities(4), None);
+		System::assert_has_event(
+			pallet_quadratic_voting::Event::ProposalEnacted(0, 0, Ok(())).into(),
+		);
+	});
+}
+
+#[test]
+fn failed_dispatches_are_recorded() {
+	new_test_ext().execute_with(|| {
+		let slash = Call::QuadraticVoting(pallet_quadratic_voting::Call::slash_proposal {
+			proposal_hash: BlakeTwo256::hash(&"Buy my NFT".encode()),
+		});
+		assert_ok!(QuadraticVoting::submit_call_proposal(Origin::signed(1), Box::new(slash)));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Aye), (1, 1, Vote::Aye)])
+		));
+		next_block();
+
+		System::assert_has_event(
+			pallet_quadratic_voting::Event::ProposalEnacted(
+				0,
+				0,
+				Err(Error::<Test>::ProposalNotInQueue.into()),
+			)
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn approved_calls_wait_for_the_enactment_period() {
+	new_test_ext().execute_with(|| {
+		EnactmentPeriod::set(&2);
+		assert_ok!(QuadraticVoting::submit_call_proposal(Origin::signed(1), create_identity(3)));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Aye)])
+		));
+		next_block();
+		System::assert_has_event(
+			pallet_quadratic_voting::Event::EnactmentScheduled(0, 0, 5).into(),
+		);
+		assert!(QuadraticVoting::enactment_of(5, (0, 0)).is_some());
+		assert_eq!(Identity::identities(3), None);
+
+		run_to_block(5);
+		assert_eq!(Identity::identities(3), Some(H256::repeat_byte(1)));
+		assert_eq!(QuadraticVoting::enactment_of(5, (0, 0)), None);
+	});
+}
+
+#[test]
+fn calls_heavier_than_the_max_enactment_weight_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let call = create_identity(3);
+		MaxEnactmentWeight::set(&(call.get_dispatch_info().weight - 1));
+		assert_noop!(
+			QuadraticVoting::submit_call_proposal(Origin::signed(1), call.clone()),
+			Error::<Test>::CallTooHeavy
+		);
+
+		assert_ok!(QuadraticVoting::note_preimage(Origin::signed(1), call.encode()));
+		assert_noop!(
+			QuadraticVoting::submit_call_proposal_hash(
+				Origin::signed(1),
+				BlakeTwo256::hash(&call.encode())
+			),
+			Error::<Test>::CallTooHeavy
+		);
+	});
+}
+
+#[test]
+fn enactments_that_do_not_fit_in_the_block_are_postponed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_call_proposal(Origin::signed(1), create_identity(3)));
+		assert_ok!(QuadraticVoting::submit_call_proposal(Origin::signed(1), create_identity(4)));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Aye), (1, 1, Vote::Aye)])
+		));
+
+		// Fill the block so that only the first enactment is dispatched
+		let max_block = <Test as frame_system::Config>::BlockWeights::get().max_block;
+		System::register_extra_weight_unchecked(max_block, DispatchClass::Mandatory);
+		next_block();
+		System::assert_has_event(
+			pallet_quadratic_voting::Event::ProposalEnacted(0, 0, Ok(())).into(),
+		);
+		System::assert_has_event(
+			pallet_quadratic_voting::Event::EnactmentPostponed(0, 1, 4).into(),
+		);
+		assert_eq!(Identity::identities(3), Some(H256::repeat_byte(1)));
+		assert_eq!(Identity::identities(4), None);
+
+		next_block();
+		System::assert_has_event(
+			pallet_quadratic_voting::Event::ProposalEnacted(0, 1, Ok(())).into(),
+		);
+		assert_eq!(Identity::identities(4), Some(H256::repeat_byte(1)));
+		assert_eq!(QuadraticVoting::enactment_of(4, (0, 1)), None);
+	});
+}
+
+#[test]
+fn approved_root_calls_are_dispatched_as_root() {
+	new_test_ext().execute_with(|| {
+		let set_storage = Box::new(Call::System(frame_system::Call::set_storage {
+			items: vec![(b"key".to_vec(), b"value".to_vec())],
+		}));
+		assert_ok!(QuadraticVoting::submit_root_call_proposal(Origin::signed(1), set_storage));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_eq!(QuadraticVoting::queued_proposals()[0].kind, ProposalKind::RootCall);
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Aye)])
+		));
+		next_block();
+
+		System::assert_has_event(
+			pallet_quadratic_voting::Event::ProposalEnacted(0, 0, Ok(())).into(),
+		);
+		assert_eq!(unhashed::get_raw(b"key"), Some(b"value".to_vec()));
+	});
+}
+
+#[test]
+fn approved_calls_are_not_dispatched_as_root() {
+	new_test_ext().execute_with(|| {
+		let set_storage = Box::new(Call::System(frame_system::Call::set_storage {
+			items: vec![(b"key".to_vec(), b"value".to_vec())],
+		}));
+		assert_ok!(QuadraticVoting::submit_call_proposal(Origin::signed(1), set_storage));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 1, Vote::Aye)])
+		));
+		next_block();
+
+		System::assert_has_event(
+			pallet_quadratic_voting::Event::ProposalEnacted(0, 0, Err(BadOrigin.into())).into(),
+		);
+		assert_eq!(unhashed::get_raw(b"key"), None);
+	});
+}
+
+#[test]
+fn only_approved_calls_are_quadratic_majorities() {
+	new_test_ext().execute_with(|| {
+		let approved = pallet_quadratic_voting::Origin::Approved(0, 1);
+		assert_eq!(EnsureQuadraticMajority::try_origin(Origin::from(approved)).ok(), Some((0, 1)));
+		assert!(EnsureQuadraticMajority::try_origin(Origin::root()).is_err());
+		assert!(EnsureQuadraticMajority::try_origin(Origin::signed(1)).is_err());
+	});
+}
+
+#[test]
+fn call_proposals_need_an_encoded_call() {
+	new_test_ext().execute_with(|| {
+		let proposal = "Should we buy DOT?".encode();
+		let proposal_hash = BlakeTwo256::hash(&proposal);
+		assert_ok!(QuadraticVoting::note_preimage(Origin::signed(1), proposal));
+		assert_noop!(
+			QuadraticVoting::submit_call_proposal_hash(Origin::signed(1), proposal_hash),
+			Error::<Test>::InvalidCall
+		);
+
+		let call = create_identity(3).encode();
+		let call_hash = BlakeTwo256::hash(&call);
+		assert_ok!(QuadraticVoting::note_preimage(Origin::signed(1), call));
+		assert_ok!(QuadraticVoting::submit_call_proposal_hash(Origin::signed(1), call_hash));
+		assert_eq!(QuadraticVoting::queued_proposals()[0].kind, ProposalKind::Call);
+	});
+}
+
+#[test]
+fn proposals_need_to_reach_quorum() {
+	new_test_ext().execute_with(|| {
+		TextApprovalRule::set(&ApprovalRule {
+			threshold: VoteThreshold::SimpleMajority,
+			quorum: Perbill::from_percent(50),
+		});
+		setup_voter(2);
+		assert_eq!(QuadraticVoting::voter_count(), 2);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 5, Vote::Aye), (1, 2, Vote::Aye)])
+		));
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(2),
+			0,
+			BoundedVec::truncate_from(vec![(0, 5, Vote::Abstain)])
+		));
+
+		// Two voters can cast 10 votes each, so half of the electorate is 10 votes
+		next_block();
+		assert!(finished_info(0, 0).approved);
+		assert_eq!(finished_info(0, 0).quorum, 10);
+		assert!(!finished_info(0, 1).approved);
+		assert_eq!(finished_info(0, 1).quorum, 10);
+	});
+}
+
+#[test]
+fn conviction_does_not_count_toward_quorum() {
+	new_test_ext().execute_with(|| {
+		TextApprovalRule::set(&ApprovalRule {
+			threshold: VoteThreshold::SimpleMajority,
+			quorum: Perbill::from_percent(50),
+		});
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes_with_conviction(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 4, Vote::Aye), (1, 5, Vote::Aye)]),
+			Conviction::Locked6x,
+			500
+		));
+		let details = QuadraticVoting::referendum_details(0).unwrap();
+		assert_eq!(details.proposals[0].tally.aye_votes, 24);
+		assert_eq!(details.proposals[0].tally.turnout, 4);
+
+		// The only voter can cast 10 votes, so half of the electorate is 5 votes
+		next_block();
+		assert!(!finished_info(0, 0).approved);
+		assert!(finished_info(0, 1).approved);
+		assert_eq!(finished_info(0, 1).tally.turnout, 5);
+	});
+}
+
+#[test]
+fn thresholds_set_the_majority_needed() {
+	let tally = |aye_votes, nay_votes| Tally {
+		aye_votes,
+		nay_votes,
+		abstain_votes: 0,
+		turnout: aye_votes + nay_votes,
+	};
+
+	assert!(VoteThreshold::SimpleMajority.approves(&tally(6, 5), 100));
+	assert!(!VoteThreshold::SimpleMajority.approves(&tally(5, 5), 100));
+
+	assert!(VoteThreshold::SuperMajority.approves(&tally(6, 3), 100));
+	assert!(!VoteThreshold::SuperMajority.approves(&tally(5, 3), 100));
+	assert!(!VoteThreshold::SuperMajority.approves(&tally(0, 0), 100));
+
+	// The lower the turnout, the larger the majority needed
+	assert!(!VoteThreshold::AdaptiveQuorumBiasing.approves(&tally(6, 4), 100));
+	assert!(VoteThreshold::AdaptiveQuorumBiasing.approves(&tally(9, 1), 100));
+	assert!(VoteThreshold::AdaptiveQuorumBiasing.approves(&tally(60, 40), 100));
+	assert!(!VoteThreshold::AdaptiveQuorumBiasing.approves(&tally(40, 60), 100));
+}
+
+#[test]
+fn referenda_are_pruned_after_the_retention_period() {
+	new_test_ext().execute_with(|| {
+		setup_voter(2);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 5, Vote::Aye), (1, 2, Vote::Nay)])
+		));
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(2),
+			0,
+			BoundedVec::truncate_from(vec![(0, 3, Vote::Nay)])
+		));
+		next_block();
+
+		// The referendum ended at block 3 and is kept for 5 blocks
+		QuadraticVoting::on_idle(7, Weight::MAX);
+		assert!(QuadraticVoting::referendum_info(0, 0).is_some());
+		assert!(QuadraticVoting::voting_of(0, 1).is_some());
+
+		QuadraticVoting::on_idle(8, Weight::MAX);
+		System::assert_last_event(pallet_quadratic_voting::Event::ReferendumPruned(0).into());
+		assert_eq!(QuadraticVoting::next_to_prune(), 1);
+		assert_eq!(QuadraticVoting::referendum_status(0), None);
+		assert_eq!(QuadraticVoting::referendum_info(0, 0), None);
+		assert_eq!(QuadraticVoting::voting_of(0, 1), None);
+		assert_eq!(QuadraticVoting::voting_of(0, 2), None);
+		assert_eq!(
+			QuadraticVoting::summary_of(0, 0),
+			Some(ProposalSummary {
+				proposal_hash: BlakeTwo256::hash(&"Let's go".encode()),
+				approved: true,
+				tally: Tally { aye_votes: 5, nay_votes: 3, abstain_votes: 0, turnout: 8 },
+			})
+		);
+		assert!(!QuadraticVoting::summary_of(0, 1).unwrap().approved);
+
+		// Referenda that haven't started yet are not pruned
+		QuadraticVoting::on_idle(100, Weight::MAX);
+		assert_eq!(QuadraticVoting::next_to_prune(), 1);
+	});
+}
+
+#[test]
+fn pruning_fits_in_the_remaining_weight() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get() + 1);
+
+		assert_eq!(QuadraticVoting::on_idle(100, 0), 0);
+		assert_eq!(QuadraticVoting::next_to_prune(), 0);
+		QuadraticVoting::on_idle(100, Weight::MAX);
+		assert_eq!(QuadraticVoting::next_to_prune(), 1);
+	});
+}
+
+#[test]
+fn only_the_latest_summaries_are_kept() {
+	new_test_ext().execute_with(|| {
+		MaxSummarizedReferenda::set(&1);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's gooo".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goooo".encode()));
+		run_to_block(2 * LaunchPeriod::get() + 1);
+
+		QuadraticVoting::on_idle(100, Weight::MAX);
+		assert!(QuadraticVoting::summary_of(0, 0).is_some());
+		QuadraticVoting::on_idle(100, Weight::MAX);
+		assert_eq!(QuadraticVoting::next_to_prune(), 2);
+		assert_eq!(QuadraticVoting::summary_of(0, 0), None);
+		assert_eq!(QuadraticVoting::summary_of(0, 1), None);
+		assert_eq!(
+			QuadraticVoting::summary_of(1, 0).unwrap().proposal_hash,
+			BlakeTwo256::hash(&"Let's gooo".encode())
+		);
+	});
+}
+
+#[test]
+fn finished_proposals_are_migrated_to_the_new_format() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<QuadraticVoting>();
+		pallet_quadratic_voting::VoterCount::<Test>::kill();
+		let old_finished = (1u8, true, 3u64).encode();
+		let old_ongoing = (0u8, H256::repeat_byte(1), 5u32, 2u32).encode();
+		unhashed::put_raw(
+			&pallet_quadratic_voting::ReferendumInfo::<Test>::hashed_key_for(0, 0),
+			&old_finished,
+		);
+		unhashed::put_raw(
+			&pallet_quadratic_voting::ReferendumInfo::<Test>::hashed_key_for(1, 0),
+			&old_ongoing,
+		);
+		// The old format only counted finished referenda, and kept the ongoing one apart
+		pallet_quadratic_voting::ReferendumCount::<Test>::put(1);
+		let active_referendum = storage_prefix(b"QuadraticVoting", b"ActiveReferendum");
+		unhashed::put_raw(&active_referendum, &[]);
+		let referendum_ends_at = storage_prefix(b"QuadraticVoting", b"ReferendumEndsAt");
+		unhashed::put(&referendum_ends_at, &5u64);
+		let old_queue = vec!["Let's go".encode()];
+		unhashed::put(&pallet_quadratic_voting::QueuedProposals::<Test>::hashed_key(), &old_queue);
+		pallet_quadratic_voting::VoterPoints::<Test>::insert(1, 75);
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(QuadraticVoting::on_chain_storage_version(), 1);
+		assert_eq!(
+			QuadraticVoting::referendum_info(0, 0),
+			Some(ProposalInfo::Finished(FinishedProposalInfo {
+				proposal_hash: Default::default(),
+				approved: true,
+				end: 3,
+				quorum: 0,
+				tally: Default::default(),
+				voters: 0,
+				points: 0,
+			}))
+		);
+		assert_eq!(QuadraticVoting::referendum_info(1, 0), Some(ProposalInfo::Cancelled(1)));
+		assert_eq!(
+			QuadraticVoting::referendum_status(0),
+			Some(ReferendumStatus::Closed { ended_at: 3 })
+		);
+		assert_eq!(
+			QuadraticVoting::referendum_status(1),
+			Some(ReferendumStatus::Cancelled { at: 1 })
+		);
+		assert_eq!(QuadraticVoting::referendum_count(), 2);
+		assert_eq!(QuadraticVoting::voter_count(), 1);
+		assert_eq!(QuadraticVoting::voter_points(1), Some(100));
+		assert!(!unhashed::exists(&active_referendum));
+		assert!(!unhashed::exists(&referendum_ends_at));
+		assert!(QuadraticVoting::queued_proposals().is_empty());
+		assert!(!unhashed::exists(&pallet_quadratic_voting::QueuedProposals::<Test>::hashed_key()));
+
+		// Migrating again does nothing
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(QuadraticVoting::referendum_info(1, 0), Some(ProposalInfo::Cancelled(1)));
+	});
+}
+
+#[test]
+fn referendum_state_can_be_queried() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(QuadraticVoting::next_launch(), 2);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		assert_eq!(QuadraticVoting::voter_status(&1).unwrap().voted_in, Vec::<u32>::new());
+		assert_eq!(QuadraticVoting::voter_status(&2), None);
+		run_to_block(LaunchPeriod::get());
+		assert_eq!(QuadraticVoting::next_launch(), 4);
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 3, Vote::Aye), (1, 1, Vote::Nay)])
+		));
+
+		let referenda = QuadraticVoting::referenda_details();
+		assert_eq!(referenda.len(), 1);
+		assert_eq!(referenda[0].index, 0);
+		assert_eq!(referenda[0].ends_at, 3);
+		assert_eq!(referenda[0].proposals.len(), 2);
+		assert_eq!(
+			referenda[0].proposals[0].proposal_hash,
+			BlakeTwo256::hash(&"Let's go".encode())
+		);
+		assert_eq!(referenda[0].proposals[0].tally.aye_votes, 3);
+		assert_eq!(referenda[0].proposals[1].tally.nay_votes, 1);
+
+		let status = QuadraticVoting::voter_status(&1).unwrap();
+		assert_eq!(status.points, 90);
+		assert_eq!(status.voted_in, vec![0]);
+	});
+}
+
+#[test]
+fn ballot_cost_and_proposal_text_can_be_queried() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(QuadraticVoting::ballot_cost(&[(3, Vote::Aye), (4, Vote::Nay)]), 25);
+		assert_eq!(QuadraticVoting::ballot_cost(&[]), 0);
+
+		let proposal = "Should we buy DOT?".encode();
+		let proposal_hash = BlakeTwo256::hash(&proposal);
+		assert_eq!(QuadraticVoting::proposal_text(proposal_hash), None);
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), proposal.clone()));
+		assert_eq!(QuadraticVoting::proposal_text(proposal_hash), Some(proposal));
+		assert_eq!(QuadraticVoting::referendum_details(0), None);
+	});
+}
+
+#[test]
+fn ballot_is_charged_the_sum_of_its_costs() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+		assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+		run_to_block(LaunchPeriod::get());
+		// 7^2 + 7^2 = 98 fits in 100 points even though (7 + 7)^2 does not
+		assert_ok!(QuadraticVoting::submit_votes(
+			Origin::signed(1),
+			0,
+			BoundedVec::truncate_from(vec![(0, 7, Vote::Aye), (1, 7, Vote::Nay)])
+		));
+		assert_eq!(QuadraticVoting::voter_points(1), Some(2));
+	});
+}
+
+#[test]
+fn vote_costs_never_overflow() {
+	use pallet_quadratic_voting::{LinearCost, QuadraticCost, VoteCost};
+
+	assert_eq!(QuadraticCost::cost(u16::MAX as u32), Some(u16::MAX as u32 * u16::MAX as u32));
+	assert_eq!(QuadraticCost::cost(u16::MAX as u32 + 1), None);
+	assert_eq!(LinearCost::cost(u32::MAX), Some(u32::MAX));
+	assert_eq!(LinearCost::ballot_cost([u32::MAX, 1]), None);
+	assert_eq!(QuadraticVoting::ballot_cost(&[(u32::MAX, Vote::Aye)]), u32::MAX);
+}
+
+/// Each proposal costs the cube of the votes cast on it
+struct CubicCost;
+
+impl pallet_quadratic_voting::VoteCost for CubicCost {
+	fn cost(votes: u32) -> Option<u32> {
+		votes.checked_pow(3)
+	}
+}
+
+#[test]
+fn custom_vote_costs_can_be_implemented() {
+	use pallet_quadratic_voting::VoteCost;
+
+	assert_eq!(CubicCost::ballot_cost([2, 3]), Some(35));
+	assert_eq!(CubicCost::ballot_cost([u32::MAX]), None);
+}
+
+/// A ballot on the first referendum of the mock, at most one vote for each of its two proposals
+fn arbitrary_ballot() -> impl Strategy<Value = Vec<(u32, u32, Vote)>> {
+	let vote = prop_oneof![Just(Vote::Aye), Just(Vote::Nay), Just(Vote::Abstain)];
+	proptest::collection::vec((0..=10u32, vote), 1..=2).prop_map(|votes| {
+		votes
+			.into_iter()
+			.enumerate()
+			.map(|(proposal_index, (amount, vote))| (proposal_index as u32, amount, vote))
+			.collect()
+	})
+}
+
+proptest! {
+	#[test]
+	fn points_never_underflow(
+		points in 0..=100u32,
+		ballots in proptest::collection::vec(arbitrary_ballot(), 1..8),
+	) {
+		new_test_ext().execute_with(|| {
+			assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's go".encode()));
+			assert_ok!(QuadraticVoting::submit_proposal(Origin::signed(1), "Let's goo".encode()));
+			run_to_block(LaunchPeriod::get());
+			pallet_quadratic_voting::VoterPoints::<Test>::insert(1, points);
+
+			// The first ballot is submitted, every later one replaces the ballot before it
+			for votes in ballots {
+				let old_ballot = pallet_quadratic_voting::VotingOf::<Test>::get(0, 1);
+				let held = old_ballot.as_ref().map_or(0, |ballot| ballot.points);
+				let available = QuadraticVoting::voter_points(1).unwrap() + held;
+				let amounts: Vec<_> =
+					votes.iter().map(|(_, amount, vote)| (*amount, *vote)).collect();
+				let cost = QuadraticVoting::ballot_cost(&amounts);
+
+				let votes = BoundedVec::truncate_from(votes);
+				let result = match old_ballot {
+					Some(_) => QuadraticVoting::change_votes(Origin::signed(1), 0, votes),
+					None => QuadraticVoting::submit_votes(Origin::signed(1), 0, votes),
+				};
+				if cost <= available {
+					assert_ok!(result);
+					assert_eq!(QuadraticVoting::voter_points(1), Some(available - cost));
+				} else {
+					assert_eq!(result, Err(Error::<Test>::NotEnoughPoints.into()));
+					assert_eq!(QuadraticVoting::voter_points(1), Some(available - held));
+				}
+
+				// Points are only ever moved between the voter and its ballot
+				let held = pallet_quadratic_voting::VotingOf::<Test>::get(0, 1)
+					.map_or(0, |ballot| ballot.points);
+				assert_eq!(QuadraticVoting::voter_points(1), Some(points - held));
+			}
+		});
+	}
+}
@@ -0,0 +1,55 @@
+//! Rules deciding whether the tally of a proposal approves it
+
+use crate::Tally;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+use sp_runtime::{PerThing, Perbill};
+
+/// Majority of the votes a proposal needs to be approved
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum VoteThreshold {
+	/// More aye than nay votes
+	SimpleMajority,
+	/// At least two thirds of the aye and nay votes are aye
+	SuperMajority,
+	/// Adaptive quorum biasing: the lower the turnout, the larger the majority needed.
+	/// Approved when `aye / sqrt(electorate) > nay / sqrt(turnout)`
+	AdaptiveQuorumBiasing,
+}
+
+impl VoteThreshold {
+	/// Whether the tally passes the threshold, given the votes the electorate can cast
+	pub fn approves(self, tally: &Tally, electorate: u32) -> bool {
+		let aye = tally.aye_votes as u128;
+		let nay = tally.nay_votes as u128;
+		match self {
+			VoteThreshold::SimpleMajority => tally.result(),
+			VoteThreshold::SuperMajority => aye > 0 && aye >= nay.saturating_mul(2),
+			VoteThreshold::AdaptiveQuorumBiasing =>
+				aye.saturating_mul(aye).saturating_mul(tally.turnout as u128) >
+					nay.saturating_mul(nay).saturating_mul(electorate as u128),
+		}
+	}
+}
+
+/// Turnout and majority a kind of proposal needs to be approved
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ApprovalRule {
+	/// Majority of the votes needed
+	pub threshold: VoteThreshold,
+	/// Part of the electorate that has to vote, abstentions included
+	pub quorum: Perbill,
+}
+
+impl ApprovalRule {
+	/// Votes needed to reach quorum, given the votes the electorate can cast
+	pub fn quorum_of(&self, electorate: u32) -> u32 {
+		self.quorum.mul_ceil(electorate)
+	}
+
+	/// Whether the tally reaches quorum and passes the threshold
+	pub fn approves(&self, tally: &Tally, electorate: u32) -> bool {
+		tally.turnout >= self.quorum_of(electorate) && self.threshold.approves(tally, electorate)
+	}
+}
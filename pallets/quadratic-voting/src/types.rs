@@ -1,20 +1,88 @@
+use crate::{Conviction, Points, ProposalIndex, ReferendumIndex};
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::RuntimeDebug;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::vec::Vec;
 
 /// Vote possibilities
-#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, TypeInfo)]
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum Vote {
 	Aye,
 	Nay,
+	/// Counts toward turnout but not toward the result
+	Abstain,
+}
+
+/// What the preimage of a proposal holds
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ProposalKind {
+	/// Text that is only recorded as approved or rejected
+	Text,
+	/// An encoded call that is dispatched once approved
+	Call,
+	/// An encoded call that is dispatched with the root origin once approved, such as a runtime
+	/// upgrade
+	RootCall,
+}
+
+impl ProposalKind {
+	/// Whether the preimage is a call to be dispatched once approved
+	pub fn is_call(self) -> bool {
+		match self {
+			ProposalKind::Text => false,
+			ProposalKind::Call | ProposalKind::RootCall => true,
+		}
+	}
+}
+
+/// A proposal waiting in the queue, along with who submitted and seconded it
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct QueuedProposal<AccountId, Balance, Hash, BlockNumber, Seconds> {
+	/// Hash of the proposal, its text is kept in the preimage store
+	pub proposal_hash: Hash,
+	/// Account that submitted the proposal
+	pub proposer: AccountId,
+	/// Amount reserved from the proposer
+	pub deposit: Balance,
+	/// Whether the proposal is text or a call
+	pub kind: ProposalKind,
+	/// Voters that seconded the proposal along with the deposit reserved from each, only their
+	/// number is kept in the queue
+	pub seconds: Seconds,
+	/// Block from which the proposal can no longer be picked for a referendum
+	pub expires_at: BlockNumber,
+}
+
+/// Text of a proposal, stored on-chain so it can be looked up by its hash
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ProposalPreimage<AccountId, Balance, Proposal> {
+	/// Text of the proposal
+	pub proposal: Proposal,
+	/// Account that noted the preimage
+	pub provider: AccountId,
+	/// Amount reserved from the provider for storing the preimage
+	pub deposit: Balance,
+	/// Number of queued proposals and ongoing referenda that refer to this preimage
+	pub references: u32,
 }
 
 #[derive(Encode, Decode, Default, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct Tally {
 	/// Number of "aye" votes
 	pub aye_votes: u32,
 	/// Number of "nay" votes
 	pub nay_votes: u32,
+	/// Number of votes abstaining
+	pub abstain_votes: u32,
+	/// Every vote cast before conviction, abstentions included. Each voter casts at most
+	/// `T::MaxVotes` on a proposal, so it never exceeds the electorate.
+	pub turnout: u32,
 }
 
 impl Tally {
@@ -29,46 +97,171 @@ pub struct OngoingProposalInfo<Hash> {
 	pub proposal_hash: Hash,
 	/// Tally of the votes
 	pub tally: Tally,
+	/// Whether the proposal is text or a call
+	pub kind: ProposalKind,
 }
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
-pub struct FinishedProposalInfo<BlockNumber> {
+pub struct FinishedProposalInfo<Hash, BlockNumber> {
+	/// Hash of the proposal
+	pub proposal_hash: Hash,
 	pub approved: bool,
 	pub end: BlockNumber,
+	/// Votes the proposal needed to reach quorum
+	pub quorum: u32,
+	/// Final tally of the votes
+	pub tally: Tally,
+	/// Number of voters that voted on the proposal
+	pub voters: u32,
+	/// Points spent on votes for the proposal
+	pub points: Points,
 }
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum ProposalInfo<Hash, BlockNumber> {
 	Ongoing(OngoingProposalInfo<Hash>),
-	Finished(FinishedProposalInfo<BlockNumber>),
+	Finished(FinishedProposalInfo<Hash, BlockNumber>),
+	/// The referendum was cancelled or vetoed at the given block
+	Cancelled(BlockNumber),
 }
 
 impl<Hash, BlockNumber> ProposalInfo<Hash, BlockNumber> {
-	pub fn get_aye_votes(&self) -> u32 {
+	/// Tally of the votes, final once the proposal has finished.
+	/// Cancelled proposals have none
+	pub fn tally(&self) -> Option<&Tally> {
 		match self {
-			ProposalInfo::Ongoing(ongoing_info) => ongoing_info.tally.aye_votes,
-			_ => unreachable!(),
+			ProposalInfo::Ongoing(ongoing_info) => Some(&ongoing_info.tally),
+			ProposalInfo::Finished(finished_info) => Some(&finished_info.tally),
+			ProposalInfo::Cancelled(_) => None,
 		}
 	}
 
+	pub fn get_aye_votes(&self) -> u32 {
+		self.tally().map_or(0, |tally| tally.aye_votes)
+	}
+
 	pub fn get_nay_votes(&self) -> u32 {
-		match self {
-			ProposalInfo::Ongoing(ongoing_info) => ongoing_info.tally.nay_votes,
-			_ => unreachable!(),
-		}
+		self.tally().map_or(0, |tally| tally.nay_votes)
+	}
+
+	pub fn get_abstain_votes(&self) -> u32 {
+		self.tally().map_or(0, |tally| tally.abstain_votes)
 	}
 
 	pub fn is_ongoing(&self) -> bool {
 		match self {
 			ProposalInfo::Ongoing(_) => true,
-			ProposalInfo::Finished(_) => false,
+			ProposalInfo::Finished(_) | ProposalInfo::Cancelled(_) => false,
 		}
 	}
 
 	pub fn has_finished(&self) -> bool {
 		match self {
-			ProposalInfo::Ongoing(_) => false,
+			ProposalInfo::Ongoing(_) | ProposalInfo::Cancelled(_) => false,
 			ProposalInfo::Finished(_) => true,
 		}
 	}
+
+	pub fn is_cancelled(&self) -> bool {
+		matches!(self, ProposalInfo::Cancelled(_))
+	}
+}
+
+/// Stage of its lifecycle a referendum is in
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ReferendumStatus<BlockNumber> {
+	/// Waiting for `starts_at` to pick its proposals, since another referendum ended in the
+	/// block it was due to start
+	Scheduled { starts_at: BlockNumber },
+	/// Accepting votes until `ends_at`
+	Voting { ends_at: BlockNumber },
+	/// Voting is over and its results are being counted
+	Tallying,
+	/// The results of its proposals are final
+	Closed { ended_at: BlockNumber },
+	/// Cancelled, vetoed or failed to start before its results were final
+	Cancelled { at: BlockNumber },
+}
+
+impl<BlockNumber> ReferendumStatus<BlockNumber> {
+	/// Whether the referendum has reached one of its final stages
+	pub fn is_final(&self) -> bool {
+		match self {
+			ReferendumStatus::Scheduled { .. } |
+			ReferendumStatus::Voting { .. } |
+			ReferendumStatus::Tallying => false,
+			ReferendumStatus::Closed { .. } | ReferendumStatus::Cancelled { .. } => true,
+		}
+	}
+}
+
+/// Compact result of a finished proposal, kept once its referendum is pruned
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ProposalSummary<Hash> {
+	/// Hash of the proposal
+	pub proposal_hash: Hash,
+	pub approved: bool,
+	/// Final tally of the votes
+	pub tally: Tally,
+}
+
+/// Live state of a proposal in an active referendum
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ProposalDetails<Hash> {
+	/// Position of the proposal in the referendum
+	pub index: ProposalIndex,
+	/// Hash of the proposal, its text is kept in the preimage store
+	pub proposal_hash: Hash,
+	/// Votes received so far
+	pub tally: Tally,
+}
+
+/// Live state of an active referendum
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ReferendumDetails<Hash, BlockNumber> {
+	/// Index of the referendum
+	pub index: ReferendumIndex,
+	/// Block at which the referendum ends
+	pub ends_at: BlockNumber,
+	/// Proposals being voted on
+	pub proposals: Vec<ProposalDetails<Hash>>,
+}
+
+/// Voting state of a registered voter
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct VoterStatus {
+	/// Points the voter has left
+	pub points: u32,
+	/// Active referenda the voter already submitted a ballot for
+	pub voted_in: Vec<ReferendumIndex>,
+}
+
+/// Ballot a voter submitted on a referendum
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Ballot<Votes, Balance> {
+	/// Votes cast on each proposal of the referendum
+	pub votes: Votes,
+	/// Points the ballot took, refunded once the referendum ends
+	pub points: u32,
+	/// Conviction every vote of the ballot is multiplied by
+	pub conviction: Conviction,
+	/// Balance locked for the conviction
+	pub balance: Balance,
+}
+
+/// Balance a voter has locked for conviction votes
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ConvictionLock<Balance, BlockNumber> {
+	/// Largest balance locked by any of the voter's ballots
+	pub balance: Balance,
+	/// Block from which the lock can be removed with `unlock`
+	pub unlock_at: BlockNumber,
 }
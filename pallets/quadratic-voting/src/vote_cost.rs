@@ -0,0 +1,39 @@
+//! Cost functions turning the votes of a ballot into the points they consume
+
+use crate::Points;
+
+/// Points it costs to cast votes on proposals.
+///
+/// The cost of a ballot is the sum of the cost of the votes cast on each proposal, so the same
+/// function is used both to check a voter has enough points and to take them.
+/// Runtimes can implement this trait for their own cost function.
+pub trait VoteCost {
+	/// Points it costs to cast `votes` on a single proposal, `None` on overflow
+	fn cost(votes: u32) -> Option<Points>;
+
+	/// Points it costs to cast a whole ballot given the votes on each of its proposals,
+	/// `None` on overflow
+	fn ballot_cost(ballot: impl IntoIterator<Item = u32>) -> Option<Points> {
+		ballot
+			.into_iter()
+			.try_fold(0, |total: Points, votes| total.checked_add(Self::cost(votes)?))
+	}
+}
+
+/// Each proposal costs the square of the votes cast on it
+pub struct QuadraticCost;
+
+impl VoteCost for QuadraticCost {
+	fn cost(votes: u32) -> Option<Points> {
+		votes.checked_mul(votes)
+	}
+}
+
+/// Each vote costs one point, regardless of how votes are distributed
+pub struct LinearCost;
+
+impl VoteCost for LinearCost {
+	fn cost(votes: u32) -> Option<Points> {
+		Some(votes)
+	}
+}
@@ -0,0 +1,696 @@
+//! Placeholder weights for pallet_quadratic_voting
+//!
+//! These numbers were written by hand from the storage each call touches, they are not the
+//! output of the benchmark CLI and have not been measured. Replace this file with the one
+//! generated by `benchmark pallet` from the benchmarks in `benchmarking.rs` (see the README)
+//! before relying on them.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_quadratic_voting.
+pub trait WeightInfo {
+	fn submit_proposal(l: u32, ) -> Weight;
+	fn submit_proposal_hash() -> Weight;
+	fn submit_call_proposal(l: u32, ) -> Weight;
+	fn submit_call_proposal_hash() -> Weight;
+	fn note_preimage(l: u32, ) -> Weight;
+	fn clear_preimage() -> Weight;
+	fn second() -> Weight;
+	fn submit_votes(v: u32, d: u32, ) -> Weight;
+	fn submit_votes_with_conviction(v: u32, d: u32, ) -> Weight;
+	fn change_votes(v: u32, o: u32, d: u32, ) -> Weight;
+	fn remove_votes(d: u32, ) -> Weight;
+	fn unlock() -> Weight;
+	fn delegate() -> Weight;
+	fn undelegate() -> Weight;
+	fn register_voter() -> Weight;
+	fn deregister_voter(r: u32, d: u32, ) -> Weight;
+	fn slash_proposal() -> Weight;
+	fn withdraw_proposal() -> Weight;
+	fn remove_proposal() -> Weight;
+	fn cancel_referendum(v: u32, ) -> Weight;
+	fn veto(v: u32, ) -> Weight;
+	fn start_referendum() -> Weight;
+	fn expire_proposals(p: u32, ) -> Weight;
+	fn end_referendum() -> Weight;
+	fn prune_referendum(v: u32, ) -> Weight;
+	fn enact_proposal() -> Weight;
+}
+
+/// Hand-written placeholder weights for pallet_quadratic_voting, not measured on any hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: QuadraticVoting VoterPoints (r:1 w:0)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn submit_proposal(l: u32, ) -> Weight {
+		(31_402_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:1 w:0)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn submit_proposal_hash() -> Weight {
+		(30_117_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:1 w:0)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn submit_call_proposal(l: u32, ) -> Weight {
+		(32_085_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:1 w:0)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn submit_call_proposal_hash() -> Weight {
+		(31_274_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:1 w:0)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn note_preimage(l: u32, ) -> Weight {
+		(24_863_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_preimage() -> Weight {
+		(22_540_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:1 w:0)
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: QuadraticVoting SecondsOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn second() -> Weight {
+		(29_118_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting DelegatedBallots (r:1 w:0)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting VotingOf (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:1 w:1)
+	// Storage: QuadraticVoting Delegators (r:1 w:0)
+	// Storage: QuadraticVoting PendingRefunds (r:1 w:1)
+	// Storage: QuadraticVoting ProposalParticipation (r:2 w:2)
+	fn submit_votes(v: u32, d: u32, ) -> Weight {
+		(23_904_000 as Weight)
+			.saturating_add((3_287_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((9_615_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(d as Weight)))
+	}
+	// Storage: QuadraticVoting DelegatedBallots (r:1 w:0)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting VotingOf (r:1 w:1)
+	// Storage: System Account (r:1 w:0)
+	// Storage: QuadraticVoting ReferendumStatusOf (r:1 w:0)
+	// Storage: QuadraticVoting ConvictionLocks (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:1 w:1)
+	// Storage: QuadraticVoting Delegators (r:1 w:0)
+	// Storage: QuadraticVoting PendingRefunds (r:1 w:1)
+	// Storage: QuadraticVoting ProposalParticipation (r:2 w:2)
+	fn submit_votes_with_conviction(v: u32, d: u32, ) -> Weight {
+		(42_318_000 as Weight)
+			.saturating_add((3_402_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((9_640_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(d as Weight)))
+	}
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting VotingOf (r:1 w:1)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting DelegatedBallots (r:1 w:1)
+	// Storage: QuadraticVoting Delegators (r:1 w:0)
+	// Storage: QuadraticVoting PendingRefunds (r:1 w:1)
+	// Storage: QuadraticVoting ProposalParticipation (r:2 w:2)
+	fn change_votes(v: u32, o: u32, d: u32, ) -> Weight {
+		(25_022_000 as Weight)
+			.saturating_add((3_392_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((3_392_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add((17_904_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(T::DbWeight::get().writes((7 as Weight).saturating_mul(d as Weight)))
+	}
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting VotingOf (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting DelegatedBallots (r:1 w:1)
+	// Storage: QuadraticVoting Delegators (r:1 w:0)
+	// Storage: QuadraticVoting PendingRefunds (r:1 w:1)
+	fn remove_votes(d: u32, ) -> Weight {
+		(31_072_000 as Weight)
+			.saturating_add((8_863_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes((6 as Weight).saturating_mul(d as Weight)))
+	}
+	// Storage: QuadraticVoting ConvictionLocks (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn unlock() -> Weight {
+		(26_391_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:2 w:1)
+	// Storage: QuadraticVoting Delegations (r:1 w:1)
+	// Storage: QuadraticVoting Delegators (r:1 w:1)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting ReferendumStatusOf (r:3 w:0)
+	// Storage: QuadraticVoting DelegatedBallots (r:3 w:3)
+	// Storage: QuadraticVoting VotingOf (r:6 w:3)
+	// Storage: QuadraticVoting PendingRefunds (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:6 w:6)
+	// Storage: QuadraticVoting ProposalParticipation (r:6 w:6)
+	fn delegate() -> Weight {
+		(96_412_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(30 as Weight))
+			.saturating_add(T::DbWeight::get().writes(22 as Weight))
+	}
+	// Storage: QuadraticVoting Delegations (r:1 w:1)
+	// Storage: QuadraticVoting Delegators (r:1 w:1)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting DelegatedBallots (r:1 w:1)
+	// Storage: QuadraticVoting VotingOf (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	fn undelegate() -> Weight {
+		(41_530_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Identity Identities (r:1 w:0)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting VoterCount (r:1 w:1)
+	fn register_voter() -> Weight {
+		(17_034_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:101 w:101)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting VotingOf (r:101 w:101)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting Delegators (r:1 w:1)
+	// Storage: QuadraticVoting DelegatedBallots (r:100 w:100)
+	// Storage: QuadraticVoting Delegations (r:1 w:101)
+	// Storage: QuadraticVoting VoterCount (r:1 w:1)
+	fn deregister_voter(r: u32, d: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((1_760_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((945_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((203 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((303 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+	}
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting SecondsOf (r:101 w:100)
+	// Storage: System Account (r:101 w:101)
+	fn slash_proposal() -> Weight {
+		(1_151_862_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(204 as Weight))
+			.saturating_add(T::DbWeight::get().writes(203 as Weight))
+	}
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting SecondsOf (r:101 w:100)
+	// Storage: System Account (r:101 w:101)
+	fn withdraw_proposal() -> Weight {
+		(1_143_517_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(204 as Weight))
+			.saturating_add(T::DbWeight::get().writes(203 as Weight))
+	}
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting SecondsOf (r:101 w:100)
+	// Storage: System Account (r:101 w:101)
+	fn remove_proposal() -> Weight {
+		(1_142_096_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(204 as Weight))
+			.saturating_add(T::DbWeight::get().writes(203 as Weight))
+	}
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumStatusOf (r:0 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting ProposalPreimages (r:2 w:2)
+	// Storage: QuadraticVoting ProposalDeposits (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: QuadraticVoting VotingOf (r:1 w:0)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	fn cancel_referendum(v: u32, ) -> Weight {
+		(43_817_000 as Weight)
+			.saturating_add((8_127_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(v as Weight)))
+	}
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumStatusOf (r:0 w:1)
+	// Storage: QuadraticVoting ProposalPreimages (r:2 w:2)
+	// Storage: QuadraticVoting ProposalDeposits (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: QuadraticVoting VotingOf (r:1 w:0)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting Blacklist (r:0 w:1)
+	// Storage: QuadraticVoting BlacklistExpiries (r:0 w:1)
+	fn veto(v: u32, ) -> Weight {
+		(47_812_000 as Weight)
+			.saturating_add((8_139_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(v as Weight)))
+	}
+	// Storage: QuadraticVoting ReferendumCount (r:1 w:1)
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:1)
+	// Storage: QuadraticVoting SecondsOf (r:202 w:200)
+	// Storage: System Account (r:200 w:200)
+	// Storage: QuadraticVoting ProposalDeposits (r:0 w:2)
+	// Storage: QuadraticVoting ReferendumInfo (r:0 w:2)
+	// Storage: QuadraticVoting ReferendumStatusOf (r:0 w:1)
+	fn start_referendum() -> Weight {
+		(2_291_744_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(405 as Weight))
+			.saturating_add(T::DbWeight::get().writes(408 as Weight))
+	}
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting SecondsOf (r:101 w:100)
+	// Storage: System Account (r:101 w:101)
+	fn expire_proposals(p: u32, ) -> Weight {
+		(6_204_000 as Weight)
+			.saturating_add((1_141_275_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((203 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((202 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: QuadraticVoting ReferendumStatusOf (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting ProposalPreimages (r:2 w:2)
+	// Storage: QuadraticVoting ProposalDeposits (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: QuadraticVoting ProposalParticipation (r:0 w:2)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:1)
+	fn end_referendum() -> Weight {
+		(57_537_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	// Storage: QuadraticVoting NextToPrune (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumCount (r:1 w:0)
+	// Storage: QuadraticVoting ReferendumStatusOf (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting DelegatedBallots (r:0 w:1)
+	// Storage: QuadraticVoting VotingOf (r:0 w:1)
+	// Storage: QuadraticVoting PendingRefunds (r:1 w:1)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumSummaries (r:0 w:4)
+	fn prune_referendum(v: u32, ) -> Weight {
+		(28_904_000 as Weight)
+			.saturating_add((7_918_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	fn enact_proposal() -> Weight {
+		(10_931_000 as Weight)
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: QuadraticVoting VoterPoints (r:1 w:0)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn submit_proposal(l: u32, ) -> Weight {
+		(31_402_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:1 w:0)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn submit_proposal_hash() -> Weight {
+		(30_117_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:1 w:0)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn submit_call_proposal(l: u32, ) -> Weight {
+		(32_085_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:1 w:0)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn submit_call_proposal_hash() -> Weight {
+		(31_274_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:1 w:0)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn note_preimage(l: u32, ) -> Weight {
+		(24_863_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn clear_preimage() -> Weight {
+		(22_540_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:1 w:0)
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: QuadraticVoting SecondsOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn second() -> Weight {
+		(29_118_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting DelegatedBallots (r:1 w:0)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting VotingOf (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:1 w:1)
+	// Storage: QuadraticVoting Delegators (r:1 w:0)
+	// Storage: QuadraticVoting PendingRefunds (r:1 w:1)
+	// Storage: QuadraticVoting ProposalParticipation (r:2 w:2)
+	fn submit_votes(v: u32, d: u32, ) -> Weight {
+		(23_904_000 as Weight)
+			.saturating_add((3_287_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((9_615_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(d as Weight)))
+	}
+	// Storage: QuadraticVoting DelegatedBallots (r:1 w:0)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting VotingOf (r:1 w:1)
+	// Storage: System Account (r:1 w:0)
+	// Storage: QuadraticVoting ReferendumStatusOf (r:1 w:0)
+	// Storage: QuadraticVoting ConvictionLocks (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:1 w:1)
+	// Storage: QuadraticVoting Delegators (r:1 w:0)
+	// Storage: QuadraticVoting PendingRefunds (r:1 w:1)
+	// Storage: QuadraticVoting ProposalParticipation (r:2 w:2)
+	fn submit_votes_with_conviction(v: u32, d: u32, ) -> Weight {
+		(42_318_000 as Weight)
+			.saturating_add((3_402_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((9_640_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(d as Weight)))
+	}
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting VotingOf (r:1 w:1)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting DelegatedBallots (r:1 w:1)
+	// Storage: QuadraticVoting Delegators (r:1 w:0)
+	// Storage: QuadraticVoting PendingRefunds (r:1 w:1)
+	// Storage: QuadraticVoting ProposalParticipation (r:2 w:2)
+	fn change_votes(v: u32, o: u32, d: u32, ) -> Weight {
+		(25_022_000 as Weight)
+			.saturating_add((3_392_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((3_392_000 as Weight).saturating_mul(o as Weight))
+			.saturating_add((17_904_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(o as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((7 as Weight).saturating_mul(d as Weight)))
+	}
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting VotingOf (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting DelegatedBallots (r:1 w:1)
+	// Storage: QuadraticVoting Delegators (r:1 w:0)
+	// Storage: QuadraticVoting PendingRefunds (r:1 w:1)
+	fn remove_votes(d: u32, ) -> Weight {
+		(31_072_000 as Weight)
+			.saturating_add((8_863_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((6 as Weight).saturating_mul(d as Weight)))
+	}
+	// Storage: QuadraticVoting ConvictionLocks (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn unlock() -> Weight {
+		(26_391_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:2 w:1)
+	// Storage: QuadraticVoting Delegations (r:1 w:1)
+	// Storage: QuadraticVoting Delegators (r:1 w:1)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting ReferendumStatusOf (r:3 w:0)
+	// Storage: QuadraticVoting DelegatedBallots (r:3 w:3)
+	// Storage: QuadraticVoting VotingOf (r:6 w:3)
+	// Storage: QuadraticVoting PendingRefunds (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:6 w:6)
+	// Storage: QuadraticVoting ProposalParticipation (r:6 w:6)
+	fn delegate() -> Weight {
+		(96_412_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(30 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(22 as Weight))
+	}
+	// Storage: QuadraticVoting Delegations (r:1 w:1)
+	// Storage: QuadraticVoting Delegators (r:1 w:1)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting DelegatedBallots (r:1 w:1)
+	// Storage: QuadraticVoting VotingOf (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	fn undelegate() -> Weight {
+		(41_530_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	// Storage: Identity Identities (r:1 w:0)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting VoterCount (r:1 w:1)
+	fn register_voter() -> Weight {
+		(17_034_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	// Storage: QuadraticVoting VoterPoints (r:101 w:101)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:0)
+	// Storage: QuadraticVoting VotingOf (r:101 w:101)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting Delegators (r:1 w:1)
+	// Storage: QuadraticVoting DelegatedBallots (r:100 w:100)
+	// Storage: QuadraticVoting Delegations (r:1 w:101)
+	// Storage: QuadraticVoting VoterCount (r:1 w:1)
+	fn deregister_voter(r: u32, d: u32, ) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((1_760_000_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add((945_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((203 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(d as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((303 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+	}
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting SecondsOf (r:101 w:100)
+	// Storage: System Account (r:101 w:101)
+	fn slash_proposal() -> Weight {
+		(1_151_862_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(204 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(203 as Weight))
+	}
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting SecondsOf (r:101 w:100)
+	// Storage: System Account (r:101 w:101)
+	fn withdraw_proposal() -> Weight {
+		(1_143_517_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(204 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(203 as Weight))
+	}
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting SecondsOf (r:101 w:100)
+	// Storage: System Account (r:101 w:101)
+	fn remove_proposal() -> Weight {
+		(1_142_096_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(204 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(203 as Weight))
+	}
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumStatusOf (r:0 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting ProposalPreimages (r:2 w:2)
+	// Storage: QuadraticVoting ProposalDeposits (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: QuadraticVoting VotingOf (r:1 w:0)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	fn cancel_referendum(v: u32, ) -> Weight {
+		(43_817_000 as Weight)
+			.saturating_add((8_127_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(v as Weight)))
+	}
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumStatusOf (r:0 w:1)
+	// Storage: QuadraticVoting ProposalPreimages (r:2 w:2)
+	// Storage: QuadraticVoting ProposalDeposits (r:2 w:2)
+	// Storage: System Account (r:2 w:2)
+	// Storage: QuadraticVoting VotingOf (r:1 w:0)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting Blacklist (r:0 w:1)
+	// Storage: QuadraticVoting BlacklistExpiries (r:0 w:1)
+	fn veto(v: u32, ) -> Weight {
+		(47_812_000 as Weight)
+			.saturating_add((8_139_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(v as Weight)))
+	}
+	// Storage: QuadraticVoting ReferendumCount (r:1 w:1)
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:1)
+	// Storage: QuadraticVoting SecondsOf (r:202 w:200)
+	// Storage: System Account (r:200 w:200)
+	// Storage: QuadraticVoting ProposalDeposits (r:0 w:2)
+	// Storage: QuadraticVoting ReferendumInfo (r:0 w:2)
+	// Storage: QuadraticVoting ReferendumStatusOf (r:0 w:1)
+	fn start_referendum() -> Weight {
+		(2_291_744_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(405 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(408 as Weight))
+	}
+	// Storage: QuadraticVoting QueuedProposals (r:1 w:1)
+	// Storage: QuadraticVoting ProposalPreimages (r:1 w:1)
+	// Storage: QuadraticVoting SecondsOf (r:101 w:100)
+	// Storage: System Account (r:101 w:101)
+	fn expire_proposals(p: u32, ) -> Weight {
+		(6_204_000 as Weight)
+			.saturating_add((1_141_275_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((203 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((202 as Weight).saturating_mul(p as Weight)))
+	}
+	// Storage: QuadraticVoting ReferendumStatusOf (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting ProposalPreimages (r:2 w:2)
+	// Storage: QuadraticVoting ProposalDeposits (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: QuadraticVoting ProposalParticipation (r:0 w:2)
+	// Storage: QuadraticVoting ActiveReferenda (r:1 w:1)
+	fn end_referendum() -> Weight {
+		(57_537_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	// Storage: QuadraticVoting NextToPrune (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumCount (r:1 w:0)
+	// Storage: QuadraticVoting ReferendumStatusOf (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumInfo (r:2 w:2)
+	// Storage: QuadraticVoting DelegatedBallots (r:0 w:1)
+	// Storage: QuadraticVoting VotingOf (r:0 w:1)
+	// Storage: QuadraticVoting PendingRefunds (r:1 w:1)
+	// Storage: QuadraticVoting VoterPoints (r:1 w:1)
+	// Storage: QuadraticVoting ReferendumSummaries (r:0 w:4)
+	fn prune_referendum(v: u32, ) -> Weight {
+		(28_904_000 as Weight)
+			.saturating_add((7_918_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(v as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(v as Weight)))
+	}
+	fn enact_proposal() -> Weight {
+		(10_931_000 as Weight)
+	}
+}
@@ -35,7 +35,12 @@ pub use frame_support::{
 	},
 	StorageValue,
 };
+use frame_support::{
+	storage::migration,
+	traits::{EitherOfDiverse, OnRuntimeUpgrade},
+};
 pub use frame_system::Call as SystemCall;
+use frame_system::EnsureRoot;
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::CurrencyAdapter;
@@ -45,6 +50,7 @@ pub use sp_runtime::{Perbill, Permill};
 
 /// Import the template pallet.
 pub use pallet_quadratic_voting;
+use pallet_quadratic_voting::{ApprovalRule, EnsureQuadraticMajority, VoteThreshold};
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -101,10 +107,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -257,30 +263,118 @@ impl pallet_transaction_payment::Config for Runtime {
 	type FeeMultiplierUpdate = ();
 }
 
-impl pallet_sudo::Config for Runtime {
+parameter_types! {
+	pub const LaunchPeriod: BlockNumber = 10;
+	pub const VotingPeriod: BlockNumber = 25;
+	pub const ProposalDepositBase: Balance = 10_000_000;
+	pub const ProposalDepositPerByte: Balance = 100_000;
+	pub const SecondDeposit: Balance = 1_000_000;
+	pub const PreimageByteDeposit: Balance = 100_000;
+	pub const ConvictionPeriod: BlockNumber = 25;
+	pub const MinConvictionBalance: Balance = 1_000_000;
+	pub const EnactmentPeriod: BlockNumber = 10;
+	// `set_code` takes a whole block
+	pub MaxEnactmentWeight: Weight = BlockWeights::get().max_block;
+	pub const TextApprovalRule: ApprovalRule = ApprovalRule {
+		threshold: VoteThreshold::SimpleMajority,
+		quorum: Perbill::from_percent(10),
+	};
+	pub const CallApprovalRule: ApprovalRule = ApprovalRule {
+		threshold: VoteThreshold::AdaptiveQuorumBiasing,
+		quorum: Perbill::from_percent(20),
+	};
+	pub const RootCallApprovalRule: ApprovalRule = ApprovalRule {
+		threshold: VoteThreshold::SuperMajority,
+		quorum: Perbill::from_percent(30),
+	};
+}
+
+/// Calls approved in a referendum, or dispatched as root by an approved root call
+pub type GovernanceOrigin = EitherOfDiverse<EnsureRoot<AccountId>, EnsureQuadraticMajority>;
+
+/// Motions approved by at least half of the technical committee, which don't have to wait for a
+/// referendum
+pub type FastTrackOrigin = EitherOfDiverse<
+	GovernanceOrigin,
+	pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 2>,
+>;
+
+/// The technical committee votes on motions, its members are kept by `TechnicalMembership`
+pub type TechnicalCollective = pallet_collective::Instance1;
+impl pallet_collective::Config<TechnicalCollective> for Runtime {
+	type Origin = Origin;
+	type Proposal = Call;
 	type Event = Event;
-	type Call = Call;
+	type MotionDuration = ConstU32<{ 3 * DAYS }>;
+	type MaxProposals = ConstU32<100>;
+	type MaxMembers = ConstU32<16>;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
 }
 
-parameter_types! {
-	pub const LaunchPeriod: BlockNumber = 10;
-	pub const VotingPeriod: BlockNumber = 3;
+/// Members of the technical committee, chosen by governance
+pub type TechnicalMembershipInstance = pallet_membership::Instance1;
+impl pallet_membership::Config<TechnicalMembershipInstance> for Runtime {
+	type Event = Event;
+	type AddOrigin = GovernanceOrigin;
+	type RemoveOrigin = GovernanceOrigin;
+	type SwapOrigin = GovernanceOrigin;
+	type ResetOrigin = GovernanceOrigin;
+	type PrimeOrigin = GovernanceOrigin;
+	type MembershipInitialized = TechnicalCommittee;
+	type MembershipChanged = TechnicalCommittee;
+	type MaxMembers = ConstU32<16>;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
 }
 
 /// Configure the pallet-template in pallets/template.
 impl pallet_quadratic_voting::Config for Runtime {
 	type Event = Event;
-	type MaxProposalLength = ConstU32<256>;
+	// Large enough for the runtime code of a `set_code` root call
+	type MaxProposalLength = ConstU32<{ 3 * 1024 * 1024 }>;
 	type LaunchPeriod = LaunchPeriod;
 	type VotingPeriod = VotingPeriod;
+	type MaxActiveReferenda = ConstU32<3>;
 	type ProposalQueueSize = ConstU32<100>;
+	type ProposalLifetime = ConstU32<{ 7 * DAYS }>;
+	type MaxExpiredProposals = ConstU32<10>;
+	type MaxSeconds = ConstU32<100>;
+	type SecondDeposit = SecondDeposit;
 	type MaxVotes = ConstU32<10>;
+	type InitialPoints = ConstU32<100>;
+	type VoteCost = pallet_quadratic_voting::QuadraticCost;
+	type MaxDelegators = ConstU32<100>;
 	type Identity = Identity;
 	type ProposalsPerReferendum = ConstU32<2>;
+	type Currency = Balances;
+	type ProposalDepositBase = ProposalDepositBase;
+	type ProposalDepositPerByte = ProposalDepositPerByte;
+	type PreimageByteDeposit = PreimageByteDeposit;
+	type ConvictionPeriod = ConvictionPeriod;
+	type MinConvictionBalance = MinConvictionBalance;
+	type RuntimeOrigin = Origin;
+	type Call = Call;
+	type EnactmentPeriod = EnactmentPeriod;
+	type MaxEnactmentWeight = MaxEnactmentWeight;
+	type ModeratorOrigin = GovernanceOrigin;
+	type TextApprovalRule = TextApprovalRule;
+	type CallApprovalRule = CallApprovalRule;
+	type RootCallApprovalRule = RootCallApprovalRule;
+	type RetentionPeriod = ConstU32<{ 30 * DAYS }>;
+	type MaxSummarizedReferenda = ConstU32<1_000>;
+	type RegistrationOrigin = GovernanceOrigin;
+	// A malicious or mistaken referendum can be stopped before it ends
+	type CancellationOrigin = FastTrackOrigin;
+	type VetoOrigin = FastTrackOrigin;
+	type CooldownPeriod = ConstU32<{ 28 * DAYS }>;
+	type WeightInfo = pallet_quadratic_voting::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_basic_identity::Config for Runtime {
 	type Event = Event;
+	type IdentityOrigin = GovernanceOrigin;
+	type OnIdentityChanged = QuadraticVoting;
+	type WeightInfo = pallet_basic_identity::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -297,10 +391,11 @@ construct_runtime!(
 		Grandpa: pallet_grandpa,
 		Balances: pallet_balances,
 		TransactionPayment: pallet_transaction_payment,
-		Sudo: pallet_sudo,
 		// Include the custom logic from the pallet-template in the runtime.
 		QuadraticVoting: pallet_quadratic_voting,
 		Identity: pallet_basic_identity,
+		TechnicalCommittee: pallet_collective::<Instance1>,
+		TechnicalMembership: pallet_membership::<Instance1>,
 	}
 );
 
@@ -332,8 +427,42 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+/// Migrations run on the next runtime upgrade, in order
+type Migrations = (RemoveSudo, pallet_quadratic_voting::migrations::v1::MigrateToV1<Runtime>);
+
+/// Removes everything `Sudo` stored, the pallet was dropped from the runtime in favour of calls
+/// approved by quadratic voting
+pub struct RemoveSudo;
+impl RemoveSudo {
+	/// Whether any key is left under the storage prefix of `Sudo`
+	fn has_storage() -> bool {
+		let prefix = sp_core::hashing::twox_128(b"Sudo");
+		frame_support::sp_io::storage::next_key(&prefix)
+			.map_or(false, |key| key.starts_with(&prefix))
+	}
+}
+
+impl OnRuntimeUpgrade for RemoveSudo {
+	fn on_runtime_upgrade() -> Weight {
+		// Only the first upgrade without `Sudo` finds its storage
+		if !Self::has_storage() {
+			return RocksDbWeight::get().reads(1);
+		}
+
+		let removed = migration::clear_storage_prefix(b"Sudo", b"", b"", None, None);
+		RocksDbWeight::get().reads_writes(1 + removed.loops as u64, removed.unique as u64)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade() -> Result<(), &'static str> {
+		frame_support::ensure!(!Self::has_storage(), "the storage of sudo was not removed");
+		Ok(())
+	}
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
@@ -346,6 +475,9 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_quadratic_voting, QuadraticVoting]
+		[pallet_basic_identity, Identity]
+		[pallet_collective, TechnicalCommittee]
+		[pallet_membership, TechnicalMembership]
 	);
 }
 
@@ -465,6 +597,40 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_quadratic_voting::runtime_api::QuadraticVotingApi<Block, AccountId, Balance, Hash, BlockNumber> for Runtime {
+		fn active_referenda() -> Vec<pallet_quadratic_voting::ReferendumDetails<Hash, BlockNumber>> {
+			QuadraticVoting::referenda_details()
+		}
+
+		fn voter_status(who: AccountId) -> Option<pallet_quadratic_voting::VoterStatus> {
+			QuadraticVoting::voter_status(&who)
+		}
+
+		fn queued_proposals() -> Vec<pallet_quadratic_voting::QueuedProposal<AccountId, Balance, Hash, BlockNumber, Vec<(AccountId, Balance)>>> {
+			QuadraticVoting::proposal_queue()
+		}
+
+		fn next_launch() -> BlockNumber {
+			QuadraticVoting::next_launch()
+		}
+
+		fn referendum(index: pallet_quadratic_voting::ReferendumIndex) -> Option<pallet_quadratic_voting::ReferendumDetails<Hash, BlockNumber>> {
+			QuadraticVoting::referendum_details(index)
+		}
+
+		fn proposal_text(proposal_hash: Hash) -> Option<Vec<u8>> {
+			QuadraticVoting::proposal_text(proposal_hash)
+		}
+
+		fn ballot_cost(votes: Vec<(u32, pallet_quadratic_voting::Vote)>) -> u32 {
+			QuadraticVoting::ballot_cost(&votes)
+		}
+
+		fn ballots(who: AccountId) -> Vec<(pallet_quadratic_voting::ReferendumIndex, pallet_quadratic_voting::Ballot<Vec<(pallet_quadratic_voting::ProposalIndex, u32, pallet_quadratic_voting::Vote)>, Balance>)> {
+			QuadraticVoting::ballots_of(&who)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,
@@ -546,3 +712,83 @@ impl_runtime_apis! {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::{assert_noop, assert_ok, traits::EnsureOrigin};
+	use sp_runtime::DispatchError;
+
+	type CancellationOrigin = <Runtime as pallet_quadratic_voting::Config>::CancellationOrigin;
+	type VetoOrigin = <Runtime as pallet_quadratic_voting::Config>::VetoOrigin;
+	type CommitteeOrigin = pallet_collective::RawOrigin<AccountId, TechnicalCollective>;
+
+	#[test]
+	fn technical_committee_can_cancel_and_veto_referenda() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			let member = AccountId::new([1; 32]);
+			let members = vec![member.clone(), AccountId::new([2; 32]), AccountId::new([3; 32])];
+			assert_ok!(TechnicalMembership::reset_members(Origin::root(), members));
+			assert_eq!(TechnicalCommittee::members().len(), 3);
+
+			// A motion approved by at least half of the committee
+			let majority: Origin = CommitteeOrigin::Members(2, 3).into();
+			let minority: Origin = CommitteeOrigin::Members(1, 3).into();
+			assert!(CancellationOrigin::try_origin(majority.clone()).is_ok());
+			assert!(VetoOrigin::try_origin(majority.clone()).is_ok());
+			assert!(CancellationOrigin::try_origin(minority.clone()).is_err());
+			assert!(VetoOrigin::try_origin(minority).is_err());
+
+			// A single member can't act alone
+			assert!(CancellationOrigin::try_origin(Origin::signed(member.clone())).is_err());
+			assert!(VetoOrigin::try_origin(Origin::signed(member.clone())).is_err());
+
+			// The slow paths are still available
+			assert!(CancellationOrigin::try_origin(Origin::root()).is_ok());
+			assert!(CancellationOrigin::try_origin(
+				pallet_quadratic_voting::Origin::Approved(0, 0).into()
+			)
+			.is_ok());
+
+			assert_noop!(
+				QuadraticVoting::cancel_referendum(majority.clone(), 0, 0),
+				pallet_quadratic_voting::Error::<Runtime>::NoActiveReferendum
+			);
+			assert_noop!(
+				QuadraticVoting::veto(majority, 0, 0, 0),
+				pallet_quadratic_voting::Error::<Runtime>::NoActiveReferendum
+			);
+			assert_noop!(
+				QuadraticVoting::cancel_referendum(Origin::signed(member), 0, 0),
+				DispatchError::BadOrigin
+			);
+		});
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn migrations_pass_their_checks_in_the_order_of_the_executive() {
+		use frame_support::traits::{GetStorageVersion, StorageVersion};
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			// A chain still running the first release
+			StorageVersion::new(0).put::<QuadraticVoting>();
+			migration::put_storage_value(b"Sudo", b"Key", &[], AccountId::new([1; 32]));
+			migration::put_storage_value(b"Sudo", b"Other", &[], 7u32);
+			let old_queue = vec![b"Let's go".to_vec()];
+			migration::put_storage_value(b"QuadraticVoting", b"QueuedProposals", &[], old_queue);
+			pallet_quadratic_voting::VoterPoints::<Runtime>::insert(AccountId::new([1; 32]), 75);
+
+			// Runs every `pre_upgrade`, then every migration, then every `post_upgrade`
+			assert_ok!(Executive::try_runtime_upgrade());
+			assert_eq!(QuadraticVoting::on_chain_storage_version(), 1);
+			assert!(!migration::have_storage_value(b"Sudo", b"Key", &[]));
+			assert!(!migration::have_storage_value(b"Sudo", b"Other", &[]));
+			assert_eq!(QuadraticVoting::voter_points(AccountId::new([1; 32])), Some(100));
+
+			// Upgrading a chain that was already migrated skips the migrations and their checks
+			assert_ok!(Executive::try_runtime_upgrade());
+			assert_eq!(QuadraticVoting::on_chain_storage_version(), 1);
+		});
+	}
+}